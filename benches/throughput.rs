@@ -0,0 +1,56 @@
+use std::time::Duration;
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use rtlola_integration::{make_float, RtlolaError, RtlolaMonitor, Value};
+
+const EVENTS: usize = 10_000;
+
+// Synthetic ball trajectory: (height, velocity, temperature) every 10ms
+fn trace() -> Result<Vec<(Vec<Value>, Duration)>, RtlolaError> {
+    (0..EVENTS)
+        .map(|i| {
+            let t = i as f64 * 0.01;
+            let inputs = vec![
+                make_float((t * 3.0).sin().abs() * 5.0)?,
+                make_float((t * 3.0).cos() * 2.0)?,
+                make_float(20.0 + (i % 200) as f64 * 0.1)?,
+            ];
+            Ok((inputs, Duration::from_secs_f64(t)))
+        })
+        .collect()
+}
+
+fn monitor() -> RtlolaMonitor {
+    let mut monitor = RtlolaMonitor::new("src/ball_spec.lola", 500, &["height", "velocity", "temperature"]).expect("ball spec");
+    monitor.start().expect("fresh monitor");
+    monitor
+}
+
+// Every iteration processes the whole trace with a fresh monitor, as event times have to increase
+fn bench(c: &mut Criterion, name: &str, run: impl Fn(&mut RtlolaMonitor, Vec<Value>, Duration) -> Result<(), RtlolaError>) {
+    let trace = trace().expect("finite inputs");
+    let mut group = c.benchmark_group("throughput");
+    group.throughput(Throughput::Elements(EVENTS as u64));
+    group.sample_size(10);
+    group.bench_function(name, |b| {
+        b.iter_batched(
+            || (monitor(), trace.clone()),
+            |(mut m, trace)| {
+                for (inputs, time) in trace {
+                    run(&mut m, inputs, time).expect("event is processed");
+                }
+            },
+            BatchSize::PerIteration,
+        )
+    });
+    group.finish();
+}
+
+fn throughput(c: &mut Criterion) {
+    bench(c, "process_event_quiet", |m, inputs, time| m.process_event_quiet(inputs, Some(time)));
+    bench(c, "process_event", |m, inputs, time| m.process_event(inputs, Some(time)).map(drop));
+    bench(c, "process_event_verdict_str", |m, inputs, time| m.process_event_verdict_str(inputs, Some(time)).map(drop));
+    bench(c, "process_event_verdict", |m, inputs, time| m.process_event_verdict(inputs, Some(time)).map(drop));
+}
+
+criterion_group!(benches, throughput);
+criterion_main!(benches);
@@ -0,0 +1,78 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+use rtlola_integration::{make_float, RtlolaError, RtlolaMonitor, Value};
+
+const EVENTS: usize = 100_000;
+
+// Counts live heap bytes and remembers the highest value since the last reset
+struct Tracking;
+
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Tracking {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            let live = LIVE.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(live, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Tracking = Tracking;
+
+// Same synthetic ball trajectory as the throughput bench, produced lazily so the trace itself costs no memory
+fn trace() -> impl Iterator<Item = (Vec<Value>, f64)> {
+    (0..EVENTS).map(|i| {
+        let t = i as f64 * 0.01;
+        let inputs = vec![
+            make_float((t * 3.0).sin().abs() * 5.0).expect("finite"),
+            make_float((t * 3.0).cos() * 2.0).expect("finite"),
+            make_float(20.0 + (i % 200) as f64 * 0.1).expect("finite"),
+        ];
+        (inputs, t)
+    })
+}
+
+fn monitor() -> Result<RtlolaMonitor, RtlolaError> {
+    let mut monitor = RtlolaMonitor::new("src/ball_spec.lola", 500, &["height", "velocity", "temperature"])?;
+    monitor.start()?;
+    Ok(monitor)
+}
+
+fn bench(name: &str, run: impl FnOnce(&mut RtlolaMonitor) -> Result<usize, RtlolaError>) -> Result<(), RtlolaError> {
+    let mut m = monitor()?;
+    let base = LIVE.load(Ordering::Relaxed);
+    PEAK.store(base, Ordering::Relaxed);
+    let start = Instant::now();
+    let reports = run(&mut m)?;
+    let elapsed = start.elapsed();
+    let peak = PEAK.load(Ordering::Relaxed) - base;
+    println!(
+        "{:<20} {:>8} reports {:>12.1} KiB peak {:>10.2?}",
+        name,
+        reports,
+        peak as f64 / 1024.0,
+        elapsed
+    );
+    Ok(())
+}
+
+fn main() -> Result<(), RtlolaError> {
+    bench("run_trace", |m| m.run_trace(trace()).map(|reports| reports.len()))?;
+    bench("for_each_verdict", |m| {
+        let mut reports = 0;
+        m.for_each_verdict(trace(), |_| reports += 1)?;
+        Ok(reports)
+    })?;
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use rtlola_integration::{make_float, RtlolaError, RtlolaMonitor, Value};
+
+const EVENTS: usize = 100_000;
+
+// Counts every heap allocation
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+// Same synthetic ball trajectory as the throughput bench, produced lazily so the trace is counted with the event
+fn trace() -> impl Iterator<Item = (Vec<Value>, Duration)> {
+    (0..EVENTS).map(|i| {
+        let t = i as f64 * 0.01;
+        let inputs = vec![
+            make_float((t * 3.0).sin().abs() * 5.0).expect("finite"),
+            make_float((t * 3.0).cos() * 2.0).expect("finite"),
+            make_float(20.0 + (i % 200) as f64 * 0.1).expect("finite"),
+        ];
+        (inputs, Duration::from_secs_f64(t))
+    })
+}
+
+fn monitor() -> Result<RtlolaMonitor, RtlolaError> {
+    let mut monitor = RtlolaMonitor::new("src/ball_spec.lola", 500, &["height", "velocity", "temperature"])?;
+    monitor.start()?;
+    Ok(monitor)
+}
+
+// Allocations of formatting every event of the trace into a verdict string
+fn count(mut run: impl FnMut(&mut RtlolaMonitor, Vec<Value>, Duration) -> Result<(), RtlolaError>) -> Result<usize, RtlolaError> {
+    let mut m = monitor()?;
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    for (inputs, time) in trace() {
+        run(&mut m, inputs, time)?;
+    }
+    Ok(ALLOCATIONS.load(Ordering::Relaxed) - before)
+}
+
+fn main() -> Result<(), RtlolaError> {
+    let owned = count(|m, inputs, time| m.process_event_verdict(inputs, Some(time)).map(drop))?;
+    let buffered = count(|m, inputs, time| m.process_event_verdict_str(inputs, Some(time)).map(drop))?;
+    println!("{:<28} {:>10} allocations", "process_event_verdict", owned);
+    println!("{:<28} {:>10} allocations", "process_event_verdict_str", buffered);
+    println!("{:<28} {:>10.2} per event", "saved", (owned as f64 - buffered as f64) / EVENTS as f64);
+    Ok(())
+}
@@ -0,0 +1,16 @@
+// With the header feature, write the C header of the FFI to include/rtlola_integration.h
+fn main() {
+    #[cfg(feature = "header")]
+    {
+        let crate_dir = std::env::var("CARGO_MANIFEST_DIR").expect("set by cargo");
+        let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir)).expect("cbindgen.toml is valid");
+        println!("cargo:rerun-if-changed=cbindgen.toml");
+        println!("cargo:rerun-if-changed=src/lib.rs");
+        cbindgen::Builder::new()
+            .with_crate(&crate_dir)
+            .with_config(config)
+            .generate()
+            .expect("the FFI can be expressed in C")
+            .write_to_file(format!("{}/include/rtlola_integration.h", crate_dir));
+    }
+}
@@ -0,0 +1,29 @@
+use rtlola_integration::{make_float, EventSource, RtlolaError, RtlolaMonitor, Value};
+use std::collections::VecDeque;
+
+// An in-memory trace as an EventSource. Sockets or database cursors implement next_event the same way.
+struct VecSource(VecDeque<(Vec<Value>, f64)>);
+
+impl EventSource for VecSource {
+    fn next_event(&mut self) -> Option<Result<(Vec<Value>, f64), RtlolaError>> {
+        self.0.pop_front().map(Ok)
+    }
+}
+
+fn main() -> Result<(), RtlolaError> {
+    let mut monitor = RtlolaMonitor::new("src/ball_spec.lola", 500, &["height", "velocity", "temperature"])?;
+    monitor.start()?;
+
+    // (height, velocity, temperature) at 1s, 2s and 3s
+    let mut events = VecDeque::new();
+    for (i, [height, velocity, temp]) in [[1.5, 2.5, 25.0], [4.2, 0.0, 29.9], [0.0, -1.5, 35.0]].into_iter().enumerate() {
+        events.push_back((vec![make_float(height)?, make_float(velocity)?, make_float(temp)?], i as f64 + 1.0));
+    }
+
+    for report in monitor.run_source(VecSource(events)) {
+        let report = report?;
+        let fired = report.fired_triggers().map(|(idx, _)| idx).collect::<Vec<_>>();
+        println!("{:.1}s -> triggers {:?}", report.time.as_secs_f64(), fired);
+    }
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+// Annotations of a spec's streams, by stream name. The frontend does not retain annotations, so they are
+// read from comment lines of the form `//@key:value` directly before an input or output declaration:
+//
+//     //@unit:meters
+//     //@description:Height above ground
+//     input height: Float64
+//
+// Blank lines and ordinary comments may separate the annotations from the declaration; any other line discards them.
+pub(crate) fn parse_annotations(spec: &str) -> HashMap<String, HashMap<String, String>> {
+    let mut annotations = HashMap::new();
+    let mut pending = HashMap::new();
+    for line in spec.lines().map(str::trim) {
+        if let Some(annotation) = line.strip_prefix("//@") {
+            let (key, value) = annotation.split_once(':').unwrap_or((annotation, ""));
+            pending.insert(key.trim().to_string(), value.trim().to_string());
+            continue;
+        }
+        if line.is_empty() || line.starts_with("//") {
+            continue;
+        }
+        let declared = ["input", "output"].iter()
+            .find_map(|keyword| line.strip_prefix(keyword).filter(|rest| rest.starts_with(char::is_whitespace)))
+            .and_then(|rest| {
+                let name = rest.trim_start().split(|c: char| !(c.is_alphanumeric() || c == '_')).next()?;
+                (!name.is_empty()).then_some(name)
+            });
+        match declared {
+            Some(name) if !pending.is_empty() => {
+                annotations.insert(name.to_string(), std::mem::take(&mut pending));
+            },
+            _ => pending.clear(),
+        }
+    }
+    annotations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rtlola_monitor::RtlolaMonitor;
+
+    #[test]
+    fn annotations_belong_to_the_next_declaration() {
+        let spec = "//@unit:meters\n//@description: Height above ground\n\n// the raw sensor value\ninput height: Float64\n\
+                    //@unit:kelvin\noutput doubled := height * 2.0\n//@unit:half\n\noutput halved := height / 2.0\n\
+                    //@unit:ignored\ntrigger halved < 0.0 \"below\"\noutput plain := height";
+        let annotations = parse_annotations(spec);
+        let height = HashMap::from([("unit".to_string(), "meters".to_string()), ("description".to_string(), "Height above ground".to_string())]);
+        assert_eq!(annotations["height"], height);
+        assert_eq!(annotations["doubled"], HashMap::from([("unit".to_string(), "kelvin".to_string())]));
+        // Blank lines keep the annotations, the trigger line discards them
+        assert_eq!(annotations["halved"], HashMap::from([("unit".to_string(), "half".to_string())]));
+        assert!(!annotations.contains_key("plain"));
+
+        let monitor = RtlolaMonitor::builder("", &["height"]).spec_text(spec).build().unwrap();
+        assert_eq!(monitor.stream_annotations("height"), Some(height));
+        assert_eq!(monitor.stream_annotations("plain"), None);
+    }
+}
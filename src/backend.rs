@@ -0,0 +1,142 @@
+use std::convert::Infallible;
+use std::time::Duration;
+use crossbeam_channel::Receiver;
+use rtlola_interpreter::input::VectorFactory;
+use rtlola_interpreter::{
+    monitor::TotalIncremental,
+    config::{OfflineMode, OnlineMode},
+    queued::{QueueError, QueuedMonitor, QueuedVerdict},
+    rtlola_mir::RtLolaMir,
+    time::{RelativeFloat, RelativeNanos},
+    ConfigBuilder, Value,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+pub(crate) type Factory = VectorFactory<Infallible, Vec<Value>>;
+pub(crate) type Verdict = QueuedVerdict<TotalIncremental, RelativeFloat>;
+
+// How the interpreter obtains event times
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MonitorMode {
+    // Event times are provided by the caller
+    #[default]
+    Offline,
+    // Event times are taken from the system clock by the interpreter, timed streams are evaluated in real time
+    Online,
+}
+
+// How the interpreter represents the event times given in offline mode. Verdict times are always RelativeFloat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum InputTime {
+    // Seconds since the start as a real number
+    #[default]
+    RelativeFloat,
+    // Whole nanoseconds since the start, for integer-timestamped data
+    RelativeNanos,
+}
+
+// The queued interpreter monitor for each supported mode and input time
+pub(crate) enum Backend {
+    Offline(QueuedMonitor<Factory, OfflineMode<RelativeFloat>, TotalIncremental, RelativeFloat>),
+    OfflineNanos(QueuedMonitor<Factory, OfflineMode<RelativeNanos>, TotalIncremental, RelativeFloat>),
+    Online(QueuedMonitor<Factory, OnlineMode, TotalIncremental, RelativeFloat>),
+}
+
+impl Backend {
+    // The input time only applies to offline mode, online mode takes the time from the system clock
+    pub(crate) fn new(ir: RtLolaMir, mode: MonitorMode, input_time: InputTime, num_inputs: usize) -> Self {
+        // The IR is parsed by the caller, ConfigBuilder::spec_str would exit the process on a parse error
+        let config = ConfigBuilder::new().with_ir(ir);
+        match (mode, input_time) {
+            (MonitorMode::Offline, InputTime::RelativeFloat) => Backend::Offline(
+                config
+                    .offline::<RelativeFloat>()
+                    .with_event_factory::<Factory>()
+                    .with_verdict::<TotalIncremental>()
+                    .queued_monitor_with_data(num_inputs),
+            ),
+            (MonitorMode::Offline, InputTime::RelativeNanos) => Backend::OfflineNanos(
+                config
+                    .offline::<RelativeNanos>()
+                    .with_event_factory::<Factory>()
+                    .with_verdict::<TotalIncremental>()
+                    .queued_monitor_with_data(num_inputs),
+            ),
+            (MonitorMode::Online, _) => Backend::Online(
+                config
+                    .online()
+                    .with_event_factory::<Factory>()
+                    .with_verdict::<TotalIncremental>()
+                    .queued_monitor_with_data(num_inputs),
+            ),
+        }
+    }
+
+    pub(crate) fn mode(&self) -> MonitorMode {
+        match self {
+            Backend::Offline(_) | Backend::OfflineNanos(_) => MonitorMode::Offline,
+            Backend::Online(_) => MonitorMode::Online,
+        }
+    }
+
+    pub(crate) fn input_time(&self) -> InputTime {
+        match self {
+            Backend::OfflineNanos(_) => InputTime::RelativeNanos,
+            Backend::Offline(_) | Backend::Online(_) => InputTime::RelativeFloat,
+        }
+    }
+
+    // The latest event time the input time can represent, RelativeNanos counts nanoseconds in a u64
+    pub(crate) fn max_time(&self) -> Option<Duration> {
+        match self {
+            Backend::OfflineNanos(_) => Some(Duration::from_nanos(u64::MAX)),
+            Backend::Offline(_) | Backend::Online(_) => None,
+        }
+    }
+
+    pub(crate) fn ir(&self) -> &RtLolaMir {
+        match self {
+            Backend::Offline(m) => m.ir(),
+            Backend::OfflineNanos(m) => m.ir(),
+            Backend::Online(m) => m.ir(),
+        }
+    }
+
+    pub(crate) fn start(&mut self) -> Result<(), QueueError> {
+        match self {
+            Backend::Offline(m) => m.start(),
+            Backend::OfflineNanos(m) => m.start(),
+            Backend::Online(m) => m.start(),
+        }
+    }
+
+    pub(crate) fn output_queue(&self) -> Receiver<Verdict> {
+        match self {
+            Backend::Offline(m) => m.output_queue(),
+            Backend::OfflineNanos(m) => m.output_queue(),
+            Backend::Online(m) => m.output_queue(),
+        }
+    }
+
+    // Block until the interpreter processed every accepted event
+    pub(crate) fn end(self) -> Result<(), QueueError> {
+        match self {
+            Backend::Offline(m) => m.end(),
+            Backend::OfflineNanos(m) => m.end(),
+            Backend::Online(m) => m.end(),
+        }
+    }
+
+    // In online mode the interpreter stamps the event itself and `time` is ignored.
+    // Times beyond max_time must be rejected by the caller, they are clamped to it here.
+    pub(crate) fn accept_event(&mut self, inputs: Vec<Value>, time: Duration) -> Result<(), QueueError> {
+        match self {
+            Backend::Offline(m) => m.accept_event(inputs, time),
+            Backend::OfflineNanos(m) => m.accept_event(inputs, u64::try_from(time.as_nanos()).unwrap_or(u64::MAX)),
+            Backend::Online(m) => m.accept_event(inputs, ()),
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use std::thread::{self, JoinHandle};
+use crossbeam_channel::{bounded, select, Receiver, Sender};
+use rtlola_interpreter::rtlola_mir::RtLolaMir;
+use crate::backend::Verdict;
+use crate::summary::RunStats;
+use crate::verdict::{PendingSources, VerdictReport};
+
+// A thread draining the verdict queue and handing every verdict to a handler as soon as it arrives.
+// Dropping it stops the thread after the verdicts already queued have been delivered.
+pub(crate) struct Background {
+    stop: Sender<()>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Background {
+    pub(crate) fn spawn<F>(receiver: Receiver<Verdict>, ir: RtLolaMir, sources: PendingSources, stats: RunStats, mut handler: F) -> Self
+    where
+        F: FnMut(VerdictReport) + Send + 'static,
+    {
+        let (stop, stop_rx) = bounded(1);
+        let thread = thread::spawn(move || {
+            let report = |verdict: Verdict| {
+                stats.record(&verdict, &ir);
+                let (seq, source) = sources.take(verdict.kind);
+                VerdictReport { seq, source, ..VerdictReport::from_queued(verdict, &ir) }
+            };
+            loop {
+                select! {
+                    recv(receiver) -> verdict => match verdict {
+                        Ok(verdict) if sources.is_tick_verdict(&verdict) => {},
+                        Ok(verdict) => handler(report(verdict)),
+                        // The monitor is gone, nothing more will arrive
+                        Err(_) => return,
+                    },
+                    recv(stop_rx) -> _ => break,
+                }
+            }
+            for verdict in receiver.try_iter().filter(|verdict| !sources.is_tick_verdict(verdict)) {
+                handler(report(verdict));
+            }
+        });
+
+        Self { stop, thread: Some(thread) }
+    }
+}
+
+impl Drop for Background {
+    fn drop(&mut self) {
+        let _ = self.stop.send(());
+        if let Some(thread) = self.thread.take() {
+            // A panicking handler only ends the background loop
+            let _ = thread.join();
+        }
+    }
+}
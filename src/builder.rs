@@ -0,0 +1,199 @@
+use std::time::Duration;
+use rtlola_interpreter::rtlola_mir::{InputReference, RtLolaMir};
+use crate::backend::{InputTime, MonitorMode};
+use crate::clock::Clock;
+#[cfg(feature = "wall-clock")]
+use crate::clock::SystemClock;
+use crate::error::RtlolaError;
+use crate::format::{ColorChoice, VerdictStyle};
+use crate::rtlola_monitor::{DuplicateTimePolicy, InputValidation, ModeCheck, OutOfOrderPolicy, OverflowPolicy, RtlolaMonitor};
+
+// Configures and creates an RtlolaMonitor
+pub struct RtlolaMonitorBuilder {
+    pub(crate) spec_path: String,
+    pub(crate) spec_text: Option<String>, // Used instead of reading spec_path when set
+    pub(crate) ir: Option<RtLolaMir>, // Used instead of parsing a specification when set
+    pub(crate) input_names: Vec<String>,
+    pub(crate) input_refs: Option<Vec<InputReference>>, // Spec input of each name, given by the caller
+    pub(crate) timeout: Option<Duration>, // None blocks until the verdict arrives
+    pub(crate) mode: MonitorMode,
+    pub(crate) input_time: InputTime,
+    pub(crate) clock: Option<Box<dyn Clock>>,
+    pub(crate) style: VerdictStyle,
+    pub(crate) strict_input_check: bool,
+    pub(crate) input_validation: InputValidation,
+    pub(crate) out_of_order: OutOfOrderPolicy,
+    pub(crate) duplicate_time: DuplicateTimePolicy,
+    pub(crate) quiet_unchanged: bool,
+    pub(crate) zero_epsilon: f64,
+    pub(crate) mode_check: ModeCheck,
+    pub(crate) verdict_limit: Option<usize>,
+    pub(crate) overflow: OverflowPolicy,
+    pub(crate) color: ColorChoice,
+    pub(crate) require_triggers: bool,
+}
+
+impl RtlolaMonitorBuilder {
+    const DEFAULT_TIMEOUT: Duration = Duration::from_millis(500);
+
+    pub fn new(spec_path: &str, input_names: &[&str]) -> Self {
+        Self {
+            spec_path: spec_path.to_string(),
+            spec_text: None,
+            ir: None,
+            input_names: input_names.iter().map(|s| s.to_string()).collect(),
+            input_refs: None,
+            timeout: Some(Self::DEFAULT_TIMEOUT),
+            mode: MonitorMode::Offline,
+            input_time: InputTime::RelativeFloat,
+            #[cfg(feature = "wall-clock")]
+            clock: Some(Box::new(SystemClock::new())),
+            #[cfg(not(feature = "wall-clock"))]
+            clock: None,
+            style: VerdictStyle::Lines,
+            strict_input_check: true,
+            input_validation: InputValidation::Lossy,
+            out_of_order: OutOfOrderPolicy::Reject,
+            duplicate_time: DuplicateTimePolicy::Allow,
+            quiet_unchanged: false,
+            zero_epsilon: RtlolaMonitor::DEFAULT_ZERO_EPSILON,
+            mode_check: ModeCheck::Warn,
+            verdict_limit: None,
+            overflow: OverflowPolicy::Block,
+            color: ColorChoice::Auto,
+            require_triggers: false,
+        }
+    }
+
+    pub fn input_names(mut self, input_names: &[&str]) -> Self {
+        self.input_names = input_names.iter().map(|s| s.to_string()).collect();
+        self
+    }
+
+    pub fn input_names_owned(mut self, input_names: Vec<String>) -> Self {
+        self.input_names = input_names;
+        self
+    }
+
+    // Name the inputs and give the spec input each name feeds, instead of matching names to spec inputs by
+    // position or by name. Building fails with RtlolaError::InputReference for references outside the spec.
+    pub fn input_mapping(mut self, mapping: Vec<(String, InputReference)>) -> Self {
+        let (names, refs) = mapping.into_iter().unzip();
+        self.input_names = names;
+        self.input_refs = Some(refs);
+        self
+    }
+
+    // Use the given specification text instead of reading the file at spec_path
+    pub fn spec_text(mut self, spec: &str) -> Self {
+        self.spec_text = Some(spec.to_string());
+        self
+    }
+
+    // Use an already parsed specification instead of reading and parsing one
+    pub fn mir(mut self, ir: RtLolaMir) -> Self {
+        self.ir = Some(ir);
+        self
+    }
+
+    // How long to wait for the verdict of an event. 0 waits without a limit, which suits offline replay
+    // where the verdict always arrives but may be delayed under load.
+    pub fn timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.timeout = (timeout_ms > 0).then(|| Duration::from_millis(timeout_ms));
+        self
+    }
+
+    pub fn mode(mut self, mode: MonitorMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    // How event times are represented in offline mode, ignored in online mode
+    pub fn input_time(mut self, input_time: InputTime) -> Self {
+        self.input_time = input_time;
+        self
+    }
+
+    // Clock used for events processed without an explicit time, the wall clock by default
+    pub fn clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Some(Box::new(clock));
+        self
+    }
+
+    // Layout of the verdict strings, one line per change by default
+    pub fn style(mut self, style: VerdictStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    // Whether building fails when the number of input names differs from the spec's inputs, true by default.
+    // When disabled, a warning is logged instead and each name is matched to the spec input of the same name.
+    // Values for names the spec does not declare are discarded and spec inputs without a name never receive
+    // a value, so a misspelled name silently leaves its stream without data.
+    pub fn strict_input_check(mut self, strict: bool) -> Self {
+        self.strict_input_check = strict;
+        self
+    }
+
+    // How string inputs that are not valid UTF-8 are handled, lossily replaced by default
+    pub fn input_validation(mut self, validation: InputValidation) -> Self {
+        self.input_validation = validation;
+        self
+    }
+
+    // What happens to events with a time before the previous event, rejected by default
+    pub fn out_of_order(mut self, policy: OutOfOrderPolicy) -> Self {
+        self.out_of_order = policy;
+        self
+    }
+
+    // What happens to events at the same time as the previous event, allowed by default
+    pub fn duplicate_time(mut self, policy: DuplicateTimePolicy) -> Self {
+        self.duplicate_time = policy;
+        self
+    }
+
+    // Return empty verdict strings for events in which no output or trigger changed
+    pub fn quiet_unchanged(mut self, quiet: bool) -> Self {
+        self.quiet_unchanged = quiet;
+        self
+    }
+
+    // Floats with a magnitude of at most epsilon are formatted as 0.0, 1e-10 by default
+    pub fn zero_epsilon(mut self, epsilon: f64) -> Self {
+        self.zero_epsilon = epsilon;
+        self
+    }
+
+    // What building does if the mode cannot handle the spec's timing constructs, e.g. a spec whose outputs
+    // are all periodic in offline mode. A warning is logged by default.
+    pub fn mode_check(mut self, check: ModeCheck) -> Self {
+        self.mode_check = check;
+        self
+    }
+
+    // Keep fewer than limit verdicts pending when an event is submitted, applying policy to make room.
+    // Without a limit, the default, verdicts queue up without bound until a consumer takes them. 0 removes the limit.
+    pub fn verdict_limit(mut self, limit: usize, policy: OverflowPolicy) -> Self {
+        self.verdict_limit = (limit > 0).then_some(limit);
+        self.overflow = policy;
+        self
+    }
+
+    // Whether verdict strings are colored, see ColorChoice. Left to `colored` by default.
+    pub fn color(mut self, color: ColorChoice) -> Self {
+        self.color = color;
+        self
+    }
+
+    // Fail with RtlolaError::NoTriggers if the spec declares no triggers, since such a monitor can never report
+    // a violation. Off by default.
+    pub fn require_triggers(mut self, require: bool) -> Self {
+        self.require_triggers = require;
+        self
+    }
+
+    pub fn build(self) -> Result<RtlolaMonitor, RtlolaError> {
+        RtlolaMonitor::from_builder(self)
+    }
+}
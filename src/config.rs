@@ -0,0 +1,219 @@
+use std::time::Duration;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use crate::backend::{InputTime, MonitorMode};
+use crate::error::RtlolaError;
+use crate::format::{ColorChoice, ColorTheme, EchoInputs, OutputFormat, VerdictStyle};
+use crate::rtlola_monitor::{
+    DuplicateTimePolicy, EdgeMode, InputValidation, ModeCheck, OutOfOrderPolicy, OverflowPolicy, PausePolicy, RtlolaMonitor,
+};
+
+// How a monitor was constructed and configured, without any runtime state. A monitor built from it with
+// RtlolaMonitor::from_config processes and formats events like the original did before its first event.
+// Settings that hold closures or writers are not part of the configuration: the clock (from_config uses the default
+// clock), custom output formatters, on_verdict callbacks, the verdict sink and the CSV sink.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MonitorConfig {
+    pub spec: String, // Source text of the specification
+    pub input_names: Vec<String>,
+    pub input_refs: Option<Vec<usize>>, // Spec input of each name if the monitor was given an input mapping
+    pub timeout_ms: u64, // 0 waits for verdicts without a limit
+    pub mode: MonitorMode,
+    pub input_time: InputTime,
+    pub style: VerdictStyle,
+    pub strict_input_check: bool,
+    pub input_validation: InputValidation,
+    pub out_of_order: OutOfOrderPolicy,
+    pub duplicate_time: DuplicateTimePolicy,
+    pub quiet_unchanged: bool,
+    pub zero_epsilon: f64,
+    pub precision: usize,
+    pub pause_policy: PausePolicy,
+    pub flush_on_drop: bool,
+    pub echo_inputs: EchoInputs,
+    pub echo_timed_inputs: bool,
+    pub mode_check: ModeCheck,
+    pub verdict_limit: usize, // 0 lets verdicts queue up without a limit
+    pub overflow: OverflowPolicy,
+    pub color: ColorChoice,
+    pub require_triggers: bool,
+    pub max_duration: Option<Duration>,
+    pub history_len: usize,
+    pub recv_retries: u32,
+    pub output_filter: Vec<String>, // Reported outputs, empty reports all
+    pub edge_modes: Vec<(String, EdgeMode)>, // Outputs not reported at every level, by name
+    pub input_transforms: Vec<(String, f64, f64)>, // Input name, scale and offset
+    pub output_units: Vec<(String, String)>, // Output name and unit
+    pub show_seq: bool,
+    pub rebase_time: bool,
+    pub show_types: bool,
+    pub theme: ColorTheme,
+}
+
+impl RtlolaMonitor {
+    // The configuration of this monitor, None if it was built from a parsed MIR and has no specification source
+    pub fn config(&self) -> Option<MonitorConfig> {
+        // Output streams by index, including triggers, as the filter and the edge modes refer to them
+        let outputs = self.ir().outputs.iter().map(|output| output.name.clone()).collect::<Vec<_>>();
+        Some(MonitorConfig {
+            spec: self.spec.clone()?,
+            input_names: self.input_names.clone(),
+            timeout_ms: self.timeout.map_or(0, |timeout| timeout.as_millis() as u64),
+            mode: self.mode(),
+            input_time: self.input_time(),
+            style: self.style(),
+            // Names only map to spec inputs by name if the strict check was disabled
+            input_refs: self.input_refs.clone(),
+            strict_input_check: self.input_map.is_none(),
+            input_validation: self.input_validation(),
+            out_of_order: self.policy.out_of_order,
+            duplicate_time: self.policy.duplicate_time,
+            quiet_unchanged: self.format.quiet_unchanged,
+            zero_epsilon: self.format.zero_epsilon,
+            precision: self.precision(),
+            pause_policy: self.pause_policy(),
+            flush_on_drop: self.flush_on_drop(),
+            echo_inputs: self.format.echo_inputs,
+            echo_timed_inputs: self.echo_timed_inputs(),
+            mode_check: self.mode_check,
+            verdict_limit: self.policy.verdict_limit.unwrap_or(0),
+            overflow: self.policy.overflow,
+            color: self.format.color,
+            require_triggers: self.require_triggers,
+            max_duration: self.policy.max_duration,
+            history_len: self.history_len(),
+            recv_retries: self.recv_retries,
+            output_filter: self.selection.filter.as_ref()
+                .map(|filter| outputs.iter().zip(filter).filter(|(_, reported)| **reported).map(|(name, _)| name.clone()).collect())
+                .unwrap_or_default(),
+            edge_modes: {
+                let mut modes = self.selection.edge_modes.iter().map(|(&idx, &mode)| (outputs[idx].clone(), mode)).collect::<Vec<_>>();
+                modes.sort_by(|a, b| a.0.cmp(&b.0));
+                modes
+            },
+            input_transforms: self.input_names.iter()
+                .zip(&self.policy.input_transforms)
+                .filter_map(|(name, transform)| transform.map(|(scale, offset)| (name.clone(), scale, offset)))
+                .collect(),
+            output_units: {
+                let mut units = self.format.output_formats.iter()
+                    .filter_map(|(name, format)| match format {
+                        OutputFormat::Unit(unit) => Some((name.clone(), unit.clone())),
+                        OutputFormat::Custom(_) => None,
+                    })
+                    .collect::<Vec<_>>();
+                units.sort();
+                units
+            },
+            show_seq: self.format.show_seq,
+            rebase_time: self.format.rebase_time,
+            show_types: self.format.show_types,
+            theme: self.theme(),
+        })
+    }
+
+    pub fn from_config(config: MonitorConfig) -> Result<Self, RtlolaError> {
+        let input_names = config.input_names.iter().map(String::as_str).collect::<Vec<_>>();
+        let mut builder = Self::builder("", &input_names);
+        if let Some(refs) = config.input_refs {
+            builder = builder.input_mapping(config.input_names.into_iter().zip(refs).collect());
+        }
+        let mut monitor = builder
+            .spec_text(&config.spec)
+            .timeout_ms(config.timeout_ms)
+            .mode(config.mode)
+            .input_time(config.input_time)
+            .style(config.style)
+            .strict_input_check(config.strict_input_check)
+            .input_validation(config.input_validation)
+            .out_of_order(config.out_of_order)
+            .duplicate_time(config.duplicate_time)
+            .quiet_unchanged(config.quiet_unchanged)
+            .zero_epsilon(config.zero_epsilon)
+            .mode_check(config.mode_check)
+            .verdict_limit(config.verdict_limit, config.overflow)
+            .color(config.color)
+            .require_triggers(config.require_triggers)
+            .build()?;
+        monitor.set_echo_inputs(config.echo_inputs);
+        monitor.set_echo_timed_inputs(config.echo_timed_inputs);
+        monitor.set_precision(config.precision);
+        monitor.set_pause_policy(config.pause_policy);
+        monitor.set_flush_on_drop(config.flush_on_drop);
+        if let Some(limit) = config.max_duration {
+            monitor.set_max_duration(limit);
+        }
+        monitor.set_history_len(config.history_len);
+        monitor.set_recv_retries(config.recv_retries);
+        monitor.set_output_filter(&config.output_filter.iter().map(String::as_str).collect::<Vec<_>>())?;
+        for (name, mode) in &config.edge_modes {
+            monitor.set_output_edge_mode(name, *mode)?;
+        }
+        for (name, scale, offset) in &config.input_transforms {
+            monitor.set_input_transform(name, *scale, *offset)?;
+        }
+        for (name, unit) in &config.output_units {
+            monitor.set_output_unit(name, unit)?;
+        }
+        monitor.set_show_seq(config.show_seq);
+        monitor.set_rebase_to_first_event(config.rebase_time);
+        monitor.set_show_types(config.show_types);
+        monitor.set_theme(config.theme);
+        Ok(monitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::unstarted_ball_monitor;
+
+    fn configured_monitor() -> RtlolaMonitor {
+        let mut monitor = unstarted_ball_monitor();
+        monitor.set_max_duration(Duration::from_secs(60));
+        monitor.set_history_len(10);
+        monitor.set_recv_retries(2);
+        monitor.set_output_filter(&["is_falling", "hot", "trigger_0"]).unwrap();
+        monitor.set_output_edge_mode("hot", EdgeMode::RisingEdge).unwrap();
+        monitor.set_output_edge_mode("is_falling", EdgeMode::BothEdges).unwrap();
+        monitor.set_input_transform("temperature", 0.1, -5.0).unwrap();
+        monitor.set_output_unit("hotInFar", " F").unwrap();
+        monitor.set_output_formatter("height_above_ground", Box::new(|value| value.to_string())).unwrap();
+        monitor.set_show_seq(true);
+        monitor.set_rebase_to_first_event(true);
+        monitor.set_show_types(true);
+        monitor.set_theme(ColorTheme::high_contrast());
+        monitor.set_precision(3);
+        monitor
+    }
+
+    #[test]
+    fn config_keeps_the_behavioural_settings() {
+        let config = configured_monitor().config().unwrap();
+        assert_eq!(config.max_duration, Some(Duration::from_secs(60)));
+        assert_eq!((config.history_len, config.recv_retries), (10, 2));
+        assert_eq!(config.output_filter, ["is_falling", "hot", "trigger_0"]);
+        assert_eq!(config.edge_modes, [("hot".to_string(), EdgeMode::RisingEdge), ("is_falling".to_string(), EdgeMode::BothEdges)]);
+        assert_eq!(config.input_transforms, [("temperature".to_string(), 0.1, -5.0)]);
+        // The custom formatter cannot be part of the configuration
+        assert_eq!(config.output_units, [("hotInFar".to_string(), " F".to_string())]);
+        assert!(config.show_seq && config.rebase_time && config.show_types);
+        assert_eq!(config.theme, ColorTheme::high_contrast());
+    }
+
+    #[test]
+    fn from_config_restores_the_settings() {
+        let config = configured_monitor().config().unwrap();
+        let restored = RtlolaMonitor::from_config(config.clone()).unwrap();
+        assert_eq!(restored.config().unwrap(), config);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn config_round_trips_through_json() {
+        let config = configured_monitor().config().unwrap();
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(serde_json::from_str::<MonitorConfig>(&json).unwrap(), config);
+    }
+}
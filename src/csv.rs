@@ -0,0 +1,127 @@
+use std::io::Write;
+use rtlola_interpreter::monitor::Change;
+use crate::error::RtlolaError;
+use crate::rtlola_monitor::RtlolaMonitor;
+use crate::verdict::VerdictReport;
+
+impl RtlolaMonitor {
+    const CSV_HEADER: &'static str = "time,stream,kind,value\n";
+
+    // Write every verdict received from now on to `writer` as CSV, starting with the header time,stream,kind,value.
+    // Each input, output change and trigger change becomes one row; triggers are named "#index" like in the table style.
+    // Times have the digits of verdict strings, nanoseconds under InputTime::RelativeNanos, but are never rebased.
+    // Rows are flushed after every verdict. A failed write is logged and does not fail the event.
+    pub fn set_csv_sink<W: Write + Send + 'static>(&mut self, mut writer: W) -> Result<(), RtlolaError> {
+        writer.write_all(Self::CSV_HEADER.as_bytes()).map_err(RtlolaError::Sink)?;
+        self.csv_sink = Some(Box::new(writer));
+        Ok(())
+    }
+
+    // Stop writing CSV rows and hand the writer back
+    pub fn take_csv_sink(&mut self) -> Option<Box<dyn Write + Send>> {
+        self.csv_sink.take()
+    }
+
+    pub(crate) fn write_csv(&mut self, report: &VerdictReport) -> Result<(), RtlolaError> {
+        let Some(mut sink) = self.csv_sink.take() else {
+            return Ok(());
+        };
+        let time = self.seconds(report.time).to_string();
+        let mut rows = String::new();
+        let mut row = |stream: &str, kind: &str, value: &str| {
+            rows.push_str(&[time.as_str(), &csv_field(stream), kind, &csv_field(value)].join(","));
+            rows.push('\n');
+        };
+        for (name, value) in &report.inputs {
+            row(name, "input", &value.to_string());
+        }
+        for output in &report.outputs {
+            let (kind, value) = self.csv_cells(&output.change);
+            row(&output.name, kind, &value);
+        }
+        for trigger in &report.triggers {
+            let (kind, value) = match &trigger.change {
+                Change::Value(_, val) => ("trigger", val.to_string()),
+                change => self.csv_cells(change),
+            };
+            row(&format!("#{}", trigger.index), kind, &value);
+        }
+        let result = sink.write_all(rows.as_bytes())
+            .and_then(|()| sink.flush())
+            .map_err(RtlolaError::Sink);
+        self.csv_sink = Some(sink);
+        result
+    }
+
+    fn csv_cells(&self, change: &Change) -> (&'static str, String) {
+        match change {
+            Change::Spawn(params) => ("spawn", self.format_params(params)),
+            Change::Value(_, val) => ("value", val.to_string()),
+            Change::Close(params) => ("close", self.format_params(params)),
+        }
+    }
+}
+
+// Quote a field that contains a separator, quote or line break, doubling the quotes inside (RFC 4180)
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use rtlola_interpreter::Value;
+    use crate::rtlola_monitor::{make_float, InputTime};
+    use crate::test_util::spec_monitor;
+
+    #[test]
+    fn csv_rows_read_back() {
+        let spec = "input status: String\noutput echo := status\ntrigger status != \"ok\" \"Not ok\"";
+        let mut monitor = spec_monitor(spec, &["status"]);
+        let file = tempfile::NamedTempFile::new().unwrap();
+        monitor.set_csv_sink(file.reopen().unwrap()).unwrap();
+        monitor.process_event(vec![Value::Str("ok".into())], Some(Duration::from_secs(1))).unwrap();
+        monitor.process_event(vec![Value::Str("slow, \"very\"\nslow".into())], Some(Duration::from_millis(2500))).unwrap();
+        assert!(monitor.take_csv_sink().is_some());
+
+        let mut reader = ::csv::Reader::from_path(file.path()).unwrap();
+        assert_eq!(reader.headers().unwrap(), vec!["time", "stream", "kind", "value"]);
+        let rows = reader.records().map(|record| record.unwrap().iter().map(str::to_string).collect::<Vec<_>>()).collect::<Vec<_>>();
+        let expected = [
+            ["1.000000", "status", "input", "ok"],
+            ["1.000000", "echo", "value", "ok"],
+            ["2.500000", "status", "input", "slow, \"very\"\nslow"],
+            ["2.500000", "echo", "value", "slow, \"very\"\nslow"],
+            ["2.500000", "#0", "trigger", "Not ok"],
+        ];
+        assert_eq!(rows, expected);
+    }
+
+    #[test]
+    fn csv_times_match_the_verdict_strings() {
+        let spec = "input x: Float64\noutput y := x";
+        let mut monitor = RtlolaMonitor::builder("", &["x"]).spec_text(spec).input_time(InputTime::RelativeNanos).build().unwrap();
+        monitor.start().unwrap();
+        let file = tempfile::NamedTempFile::new().unwrap();
+        monitor.set_csv_sink(file.reopen().unwrap()).unwrap();
+        let verdict = monitor.process_event_verdict(vec![make_float(1.0).unwrap()], Some(Duration::from_nanos(1_000_000_001))).unwrap();
+        assert!(verdict.starts_with("[1.000000001s]"), "{}", verdict);
+        monitor.take_csv_sink();
+
+        let mut reader = ::csv::Reader::from_path(file.path()).unwrap();
+        let times = reader.records().map(|record| record.unwrap()[0].to_string()).collect::<Vec<_>>();
+        assert_eq!(times, ["1.000000001", "1.000000001"]);
+    }
+
+    #[test]
+    fn fields_are_quoted_only_when_needed() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
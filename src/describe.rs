@@ -0,0 +1,84 @@
+use serde::Serialize;
+use rtlola_interpreter::rtlola_mir::OutputKind;
+use crate::rtlola_monitor::RtlolaMonitor;
+
+#[derive(Debug, Serialize)]
+pub struct SpecDescription {
+    pub inputs: Vec<StreamDescription>,
+    pub outputs: Vec<OutputDescription>,
+    pub triggers: Vec<TriggerDescription>,
+    pub analysis: SpecAnalysis,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StreamDescription {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OutputDescription {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+    pub params: Vec<StreamDescription>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TriggerDescription {
+    pub index: usize,
+    pub message: String,
+}
+
+// Properties of the spec derived from the MIR
+#[derive(Debug, Serialize)]
+pub struct SpecAnalysis {
+    pub time_driven: bool,
+    pub event_driven_streams: usize,
+    pub time_driven_streams: usize,
+    pub sliding_windows: usize,
+    pub discrete_windows: usize,
+}
+
+impl RtlolaMonitor {
+    pub fn describe(&self) -> SpecDescription {
+        let ir = self.ir();
+        SpecDescription {
+            inputs: ir.inputs.iter()
+                .map(|input| StreamDescription { name: input.name.clone(), ty: input.ty.to_string() })
+                .collect(),
+            outputs: ir.outputs.iter()
+                .filter(|output| matches!(output.kind, OutputKind::NamedOutput(_)))
+                .map(|output| OutputDescription {
+                    name: output.name.clone(),
+                    ty: output.ty.to_string(),
+                    params: output.params.iter()
+                        .map(|param| StreamDescription { name: param.name.clone(), ty: param.ty.to_string() })
+                        .collect(),
+                })
+                .collect(),
+            triggers: self.triggers().into_iter()
+                .map(|(index, message)| TriggerDescription { index, message })
+                .collect(),
+            analysis: SpecAnalysis {
+                time_driven: ir.has_time_driven_features(),
+                event_driven_streams: ir.event_driven.len(),
+                time_driven_streams: ir.time_driven.len(),
+                sliding_windows: ir.sliding_windows.len(),
+                discrete_windows: ir.discrete_windows.len(),
+            },
+        }
+    }
+
+    // Inputs, outputs, triggers and analysis of the spec as a single JSON document
+    pub fn describe_json(&self) -> String {
+        serde_json::to_string(&self.describe()).expect("spec description is always serializable")
+    }
+
+    // The verdict history as a JSON object {"history_len": n, "verdicts": [...]} with the verdicts oldest first
+    pub fn history_json(&self) -> String {
+        let verdicts = self.history().collect::<Vec<_>>();
+        serde_json::json!({ "history_len": self.history_len(), "verdicts": verdicts }).to_string()
+    }
+}
@@ -0,0 +1,125 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum RtlolaError {
+    #[error("Failed to read specification file {0}: {1}")]
+    SpecRead(String, #[source] std::io::Error),
+
+    #[error("Failed to parse specification: {0}")]
+    Parse(String),
+
+    #[error("Spec requires {expected} inputs but {got} names provided")]
+    InputNames { expected: usize, got: usize },
+
+    #[error("Expected {expected} inputs, got {got}")]
+    InputCount { expected: usize, got: usize },
+
+    #[error("Input {name} expects {expected}, got {got}")]
+    InputType { name: String, expected: String, got: String },
+
+    #[error("Unknown input stream {0}")]
+    UnknownInput(String),
+
+    #[error("Unknown output stream {0}")]
+    UnknownOutput(String),
+
+    #[error("Invalid record on line {line}: {reason}")]
+    Record { line: usize, reason: String },
+
+    #[error("Float input is NaN")]
+    NanFloat,
+
+    #[error("String input {0} is not valid UTF-8")]
+    InvalidUtf8(usize),
+
+    #[error("String input {0} is NULL")]
+    NullString(usize),
+
+    #[error("Unknown input type tag {0}")]
+    InvalidType(u32),
+
+    #[error("Failed to start monitor: {0}")]
+    Start(String),
+
+    // event counts the events accepted before, the reason is the interpreter's error
+    #[error("Failed to accept event {event} at {time:?}: {reason}")]
+    AcceptEvent { event: u64, time: std::time::Duration, reason: String },
+
+    #[error("Event time {got:?} is before the previous event time {prev:?}")]
+    TimeRegression { prev: std::time::Duration, got: std::time::Duration },
+
+    #[error("Event time {got:?} exceeds the maximum duration {limit:?}")]
+    DurationExceeded { limit: std::time::Duration, got: std::time::Duration },
+
+    #[error("The specification cannot be monitored correctly in this mode: {reason}")]
+    ModeIncompatible { reason: String },
+
+    #[error("The specification declares no triggers")]
+    NoTriggers,
+
+    #[error("The monitor is paused")]
+    Paused,
+
+    #[error("Event time {0:?} equals the previous event's time")]
+    DuplicateTimestamp(std::time::Duration),
+
+    #[error("Event time {0} is not a finite, non-negative number of seconds")]
+    InvalidTime(f64),
+
+    #[error("Input {name} is mapped to input reference {reference}, but the spec declares {inputs} inputs")]
+    InputReference { name: String, reference: usize, inputs: usize },
+
+    #[error("No event time given and the monitor has no clock")]
+    MissingTime,
+
+    #[error("Verdicts are being consumed by the background consumer")]
+    BackgroundRunning,
+
+    #[error("No verdict sink set")]
+    NoSink,
+
+    #[error("Failed to write verdict to sink: {0}")]
+    Sink(#[source] std::io::Error),
+
+    #[error("Timeout while waiting for verdict")]
+    Timeout,
+
+    #[error("Monitor channel disconnected")]
+    Disconnected,
+}
+
+impl RtlolaError {
+    // Stable numeric code of the error for C callers, always negative
+    pub fn code(&self) -> i32 {
+        match self {
+            RtlolaError::SpecRead(..) => -1,
+            RtlolaError::Parse(_) => -2,
+            RtlolaError::InputNames { .. } => -3,
+            RtlolaError::InputCount { .. } => -4,
+            RtlolaError::InputType { .. } => -5,
+            RtlolaError::UnknownInput(_) => -6,
+            RtlolaError::NanFloat => -7,
+            RtlolaError::InvalidType(_) => -8,
+            RtlolaError::Start(_) => -9,
+            RtlolaError::AcceptEvent { .. } => -10,
+            RtlolaError::MissingTime => -11,
+            RtlolaError::BackgroundRunning => -12,
+            RtlolaError::Timeout => -13,
+            RtlolaError::Disconnected => -14,
+            RtlolaError::UnknownOutput(_) => -15,
+            RtlolaError::InvalidUtf8(_) => -16,
+            RtlolaError::Record { .. } => -17,
+            RtlolaError::TimeRegression { .. } => -18,
+            RtlolaError::NoSink => -19,
+            RtlolaError::Sink(_) => -20,
+            RtlolaError::DurationExceeded { .. } => -21,
+            RtlolaError::ModeIncompatible { .. } => -22,
+            RtlolaError::NullString(_) => -23,
+            RtlolaError::NoTriggers => -24,
+            RtlolaError::Paused => -25,
+            RtlolaError::DuplicateTimestamp(_) => -26,
+            RtlolaError::InputReference { .. } => -27,
+            RtlolaError::InvalidTime(_) => -28,
+        }
+    }
+}
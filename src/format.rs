@@ -0,0 +1,436 @@
+use std::collections::HashMap;
+use std::fmt::{self, Write};
+use std::time::Duration;
+use rtlola_interpreter::{monitor::Change, queued::VerdictKind, rtlola_mir::{OutputKind, Type}, Value};
+#[cfg(feature = "color")]
+use colored::*;
+#[cfg(feature = "color")]
+pub use colored::Color;
+#[cfg(not(feature = "color"))]
+pub use crate::nocolor::Color;
+use crate::rtlola_monitor::RtlolaMonitor;
+use crate::verdict::VerdictReport;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// Layout of the verdict strings returned by process_event_verdict
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum VerdictStyle {
+    // One line per input, output and trigger change
+    #[default]
+    Lines,
+    // One aligned table per verdict with the columns stream, kind and value
+    Table,
+    // One logfmt line per verdict and per output and trigger change, e.g.
+    // `time=1.000000 kind=trigger stream=#2 value="Ball above ground" msg="Ball above ground"`. Never colored.
+    Logfmt,
+}
+
+// Settings of the verdict strings
+pub(crate) struct FormatSettings {
+    pub(crate) style: VerdictStyle,
+    pub(crate) theme: ColorTheme,
+    pub(crate) color: ColorChoice,
+    pub(crate) echo_inputs: EchoInputs,
+    pub(crate) echo_timed_inputs: bool, // Whether timed verdicts list the latest inputs under the echo setting
+    pub(crate) output_formats: HashMap<String, OutputFormat>, // Per-output overrides of format_number, e.g. units
+    pub(crate) quiet_unchanged: bool, // Return an empty verdict string for verdicts without output changes
+    pub(crate) show_seq: bool, // Prefix event verdict strings with the event's sequence number
+    pub(crate) rebase_time: bool, // Show times relative to the first accepted event
+    pub(crate) show_types: bool, // Annotate values with their stream's type
+    pub(crate) zero_epsilon: f64, // Floats with a magnitude up to this are formatted as 0.0
+    pub(crate) precision: usize, // Decimals of formatted floats
+}
+
+impl FormatSettings {
+    // Color an element of a verdict string under the monitor's color choice
+    pub(crate) fn paint(&self, text: &str, color: Color) -> String {
+        #[cfg(feature = "color")]
+        match self.color {
+            ColorChoice::Auto => text.color(color).to_string(),
+            ColorChoice::Always => format!("\x1b[{}m{}\x1b[0m", color.to_fg_str(), text),
+            ColorChoice::Never => text.to_string(),
+        }
+        #[cfg(not(feature = "color"))]
+        {
+            let _ = color;
+            text.to_string()
+        }
+    }
+}
+
+// Presentation of a named output's values in verdict strings
+pub enum OutputFormat {
+    // Replaces the default number formatting
+    Custom(Box<dyn Fn(&Value) -> String + Send>),
+    // Appended to the default number formatting, e.g. " m/s"
+    Unit(String),
+}
+
+// Which inputs the Lines style echoes for an event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EchoInputs {
+    #[default]
+    All,
+    // Only inputs whose value differs from the previous formatted event
+    Changed,
+    None,
+}
+
+// Whether a monitor's verdict strings are colored. Each monitor decides for its own strings, independent of the
+// other monitors in the process. Auto leaves the decision to `colored`, which colors only terminals and honors
+// NO_COLOR and CLICOLOR_FORCE. Without the `color` feature verdict strings are never colored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ColorChoice {
+    #[default]
+    Auto,
+    // Also when the output is not a terminal, e.g. when piped into a color-aware pager
+    Always,
+    Never,
+}
+
+// Colors of the elements of verdict strings, set with RtlolaMonitor::set_theme.
+// Without the `color` feature the theme is kept but no colors are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ColorTheme {
+    #[cfg_attr(feature = "serde", serde(with = "ColorDef"))]
+    pub trigger: Color, // Trigger lines and the deadline line of timed verdicts
+    #[cfg_attr(feature = "serde", serde(with = "ColorDef"))]
+    pub input: Color,
+    #[cfg_attr(feature = "serde", serde(with = "ColorDef"))]
+    pub output: Color, // Output names
+    #[cfg_attr(feature = "serde", serde(with = "ColorDef"))]
+    pub value: Color, // The value marker of output changes
+    #[cfg_attr(feature = "serde", serde(with = "ColorDef"))]
+    pub spawn: Color,
+    #[cfg_attr(feature = "serde", serde(with = "ColorDef"))]
+    pub close: Color,
+}
+
+// Serde representation of Color, which `colored` does not provide
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Color")]
+enum ColorDef {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+    TrueColor { r: u8, g: u8, b: u8 },
+}
+
+impl Default for ColorTheme {
+    fn default() -> Self {
+        Self {
+            trigger: Color::Red,
+            input: Color::Cyan,
+            output: Color::Blue,
+            value: Color::Green,
+            spawn: Color::Magenta,
+            close: Color::Yellow,
+        }
+    }
+}
+
+impl ColorTheme {
+    // Bright variants of the default colors, readable on dark backgrounds
+    pub fn high_contrast() -> Self {
+        Self {
+            trigger: Color::BrightRed,
+            input: Color::BrightCyan,
+            output: Color::BrightWhite,
+            value: Color::BrightGreen,
+            spawn: Color::BrightMagenta,
+            close: Color::BrightYellow,
+        }
+    }
+
+    // The Okabe-Ito palette, which stays distinguishable under the common forms of color blindness.
+    // Terminals that do not announce true color support in COLORTERM get the nearest ANSI colors.
+    pub fn colorblind_safe() -> Self {
+        Self {
+            trigger: Color::TrueColor { r: 230, g: 159, b: 0 },
+            input: Color::TrueColor { r: 86, g: 180, b: 233 },
+            output: Color::TrueColor { r: 0, g: 114, b: 178 },
+            value: Color::TrueColor { r: 0, g: 158, b: 115 },
+            spawn: Color::TrueColor { r: 204, g: 121, b: 167 },
+            close: Color::TrueColor { r: 240, g: 228, b: 66 },
+        }
+    }
+}
+
+impl RtlolaMonitor {
+    const KIND_WIDTH: usize = 7;
+
+    // Format the value of a named output with its OutputFormat, if one is set
+    pub(crate) fn format_output(&self, name: &str, val: Value) -> String {
+        match self.format.output_formats.get(name) {
+            Some(OutputFormat::Custom(f)) => f(&val),
+            Some(OutputFormat::Unit(unit)) => format!("{}{}", self.format_number(val, Self::DEFAULT_THRESHOLD), unit),
+            None => self.format_number(val, Self::DEFAULT_THRESHOLD),
+        }
+    }
+
+    // Append the type to a formatted value if types are shown
+    pub(crate) fn typed(&self, value: String, ty: &Type) -> String {
+        match self.format.show_types {
+            true => format!("{}:{}", value, ty),
+            false => value,
+        }
+    }
+
+    // Format the parameters of a spawned or closed instance as a tuple, e.g. "(3, 2.500000)"
+    pub(crate) fn format_params(&self, params: &[Value]) -> String {
+        let params = params.iter()
+            .map(|param| self.format_number(param.clone(), Self::DEFAULT_THRESHOLD))
+            .collect::<Vec<_>>();
+        format!("({})", params.join(", "))
+    }
+
+    // Write the output and trigger changes of a verdict as a table.
+    // Cells are padded before they are colored so the escape codes do not break the alignment.
+    pub(crate) fn write_table(&self, out: &mut String, report: &VerdictReport, ts: Seconds) -> fmt::Result {
+        write!(out, "[{}s] ", ts)?;
+        if let Some(seq) = report.seq {
+            write!(out, "[#{}] ", seq)?;
+        }
+        match (report.kind, &report.source) {
+            (VerdictKind::Timed, _) => writeln!(out, "{}", deadline_text(report.outputs.len() + report.triggers.len()))?,
+            (VerdictKind::Event, Some(source)) => writeln!(out, "Event [source={}]", source)?,
+            (VerdictKind::Event, None) => writeln!(out, "Event")?,
+        }
+
+        let mut rows = Vec::new();
+        for output in &report.outputs {
+            let (kind, mut value) = self.table_cells(&output.name, &output.change);
+            if let Change::Value(..) = output.change
+                && let Some(stream) = self.ir().outputs.iter().find(|stream| stream.name == output.name)
+            {
+                value = self.typed(value, &stream.ty);
+            }
+            rows.push((output.name.clone(), kind, value));
+        }
+        for trigger in &report.triggers {
+            let (kind, value) = match &trigger.change {
+                Change::Value(_, val) => ("trigger", val.to_string()),
+                change => self.table_cells("", change),
+            };
+            rows.push((format!("#{}", trigger.index), kind, value));
+        }
+        if rows.is_empty() {
+            return Ok(());
+        }
+
+        let width = rows.iter().map(|(stream, _, _)| stream.chars().count()).max().unwrap_or(0).max("stream".len());
+        writeln!(out, "  {:<width$}  {:<kind$}  value", "stream", "kind", kind = Self::KIND_WIDTH)?;
+        for (stream, kind, value) in rows {
+            let cell = format!("{:<kind_width$}", kind, kind_width = Self::KIND_WIDTH);
+            let cell = match kind {
+                "trigger" => self.format.paint(&cell, self.format.theme.trigger),
+                "spawn" => self.format.paint(&cell, self.format.theme.spawn),
+                "close" => self.format.paint(&cell, self.format.theme.close),
+                _ => self.format.paint(&cell, self.format.theme.value),
+            };
+            writeln!(out, "  {:<width$}  {}  {}", stream, cell, value)?;
+        }
+        Ok(())
+    }
+
+    // Write a verdict as logfmt lines: one for the verdict itself, then one per output and trigger change
+    pub(crate) fn write_logfmt(&self, out: &mut String, report: &VerdictReport, ts: Seconds) -> fmt::Result {
+        let time = format!("time={}", ts);
+        match report.kind {
+            VerdictKind::Timed => write!(out, "{} kind=deadline", time)?,
+            VerdictKind::Event => write!(out, "{} kind=event", time)?,
+        }
+        if let Some(seq) = report.seq {
+            write!(out, " seq={}", seq)?;
+        }
+        if let Some(source) = &report.source {
+            write!(out, " source={}", logfmt_value(source))?;
+        }
+        writeln!(out)?;
+
+        for output in &report.outputs {
+            let (kind, mut value) = self.table_cells(&output.name, &output.change);
+            if let Change::Value(..) = output.change
+                && let Some(stream) = self.ir().outputs.iter().find(|stream| stream.name == output.name)
+            {
+                value = self.typed(value, &stream.ty);
+            }
+            writeln!(out, "{} kind={} stream={} value={}", time, kind, logfmt_value(&output.name), logfmt_value(&value))?;
+        }
+        for trigger in &report.triggers {
+            let stream = format!("#{}", trigger.index);
+            match &trigger.change {
+                Change::Value(_, val) => {
+                    let output = self.ir().outputs.iter()
+                        .find(|output| output.kind == OutputKind::Trigger(trigger.index))
+                        .expect("reports only contain triggers of the spec");
+                    let value = self.format_output(&output.name, val.clone());
+                    let msg = Self::trigger_text(output, val.clone());
+                    writeln!(out, "{} kind=trigger stream={} value={} msg={}", time, stream, logfmt_value(&value), logfmt_value(&msg))?;
+                },
+                change => {
+                    let (kind, value) = self.table_cells("", change);
+                    writeln!(out, "{} kind={} stream={} value={}", time, kind, stream, logfmt_value(&value))?;
+                },
+            }
+        }
+        Ok(())
+    }
+
+    fn table_cells(&self, name: &str, change: &Change) -> (&'static str, String) {
+        match change {
+            Change::Spawn(param) => ("spawn", self.format_params(param)),
+            Change::Value(_, val) => ("value", self.format_output(name, val.clone())),
+            Change::Close(param) => ("close", self.format_params(param)),
+        }
+    }
+}
+
+// A logfmt value, quoted if it is empty or contains spaces, quotes, equals signs or control characters
+fn logfmt_value(value: &str) -> String {
+    let plain = !value.is_empty() && !value.chars().any(|c| c == ' ' || c == '"' || c == '=' || c.is_control());
+    if plain {
+        return value.to_string();
+    }
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+// Header of a timed verdict, naming how many output and trigger changes the deadline produced
+pub(crate) fn deadline_text(changes: usize) -> String {
+    match changes {
+        0 => "Deadline reached, no changes".to_string(),
+        1 => "Deadline reached, 1 change".to_string(),
+        n => format!("Deadline reached, {} changes", n),
+    }
+}
+
+// A verdict time in seconds, formatted from the Duration itself so no digits are lost to a float conversion.
+// RelativeNanos input times are shown to the nanosecond, all others to the microsecond.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Seconds {
+    pub(crate) time: Duration,
+    pub(crate) nanos: bool,
+}
+
+impl fmt::Display for Seconds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.nanos {
+            write!(f, "{}.{:09}", self.time.as_secs(), self.time.subsec_nanos())
+        } else {
+            write!(f, "{}.{:06}", self.time.as_secs(), self.time.subsec_micros())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rtlola_monitor::make_float;
+    use crate::test_util::{ball_builder, ball_event, BALL_SPEC};
+
+    fn styled_monitor(style: VerdictStyle) -> RtlolaMonitor {
+        let mut monitor = ball_builder().style(style).color(ColorChoice::Never).build().unwrap();
+        monitor.start().unwrap();
+        monitor
+    }
+
+    #[test]
+    fn logfmt_trigger_lines_carry_the_trigger_value() {
+        let mut monitor = styled_monitor(VerdictStyle::Logfmt);
+        let out = monitor.process_event_verdict(ball_event(1.0, -1.0, 20.0), Some(Duration::from_secs(1))).unwrap();
+        assert!(out.lines().any(|line| line == r#"time=1.000000 kind=trigger stream=#0 value="Ball is falling" msg="Ball is falling""#), "{}", out);
+    }
+
+
+    #[test]
+    fn spawn_parameters_are_formatted_as_a_tuple() {
+        let spec = "input a: UInt64\ninput b: Float64\noutput y(p: UInt64, q: Float64) spawn with (a, b) eval @a with p";
+        let mut monitor = RtlolaMonitor::builder("", &["a", "b"]).spec_text(spec).color(ColorChoice::Never).build().unwrap();
+        monitor.start().unwrap();
+        let out = monitor.process_event_verdict(vec![Value::Unsigned(3), make_float(2.5).unwrap()], Some(Duration::from_secs(1))).unwrap();
+        assert!(out.contains("[Output][y] [Spawn] (3, 2.500000)\n"), "{}", out);
+        assert_eq!(monitor.format_params(&[]), "()");
+    }
+
+
+    #[test]
+    fn types_are_shown_only_when_enabled() {
+        let spec = "input n: UInt64\ninput x: Float64\noutput next := n + 1\noutput doubled := x * 2.0";
+        let mut monitor = RtlolaMonitor::builder("", &["n", "x"]).spec_text(spec).color(ColorChoice::Never).build().unwrap();
+        monitor.start().unwrap();
+        let event = || vec![Value::Unsigned(3), make_float(1.5).unwrap()];
+        let plain = monitor.process_event_verdict(event(), Some(Duration::from_secs(1))).unwrap();
+        assert!(plain.contains("[Input] [n] = 3\n") && plain.contains("[Output][next] [Value] =  4\n"), "{}", plain);
+        assert!(!plain.contains(":UInt64") && !plain.contains(":Float64"), "{}", plain);
+
+        monitor.set_show_types(true);
+        let typed = monitor.process_event_verdict(event(), Some(Duration::from_secs(2))).unwrap();
+        for line in ["[Input] [n] = 3:UInt64\n", "[Input] [x] = 1.500000:Float64\n", "[Output][next] [Value] =  4:UInt64\n", "[Output][doubled] [Value] =  3.000000:Float64\n"] {
+            assert!(typed.contains(line), "{} missing in\n{}", line, typed);
+        }
+    }
+
+
+    #[test]
+    fn color_is_left_to_colored_by_default() {
+        assert_eq!(ColorChoice::default(), ColorChoice::Auto);
+        assert_eq!(RtlolaMonitor::builder(BALL_SPEC, &[]).color, ColorChoice::Auto);
+    }
+
+    #[cfg(feature = "color")]
+    #[test]
+    fn each_monitor_applies_its_own_color_choice() {
+        let mut colored = ball_builder().color(ColorChoice::Always).build().unwrap();
+        colored.start().unwrap();
+        let mut plain = styled_monitor(VerdictStyle::Lines);
+        // Test output is captured, so it is not a terminal
+        for secs in 1..=2 {
+            let time = Some(Duration::from_secs(secs));
+            let out = colored.process_event_verdict(ball_event(1.0, -1.0, 20.0), time).unwrap();
+            assert!(out.contains("\u{1b}[31m[Trigger]\u{1b}[0m"), "{:?}", out);
+            let out = plain.process_event_verdict(ball_event(1.0, -1.0, 20.0), time).unwrap();
+            assert!(!out.contains('\u{1b}'), "{:?}", out);
+            assert!(out.contains("[Trigger] [#0] Ball is falling\n"), "{}", out);
+        }
+    }
+
+
+    #[test]
+    fn deadline_text_counts_the_changes() {
+        assert_eq!(deadline_text(0), "Deadline reached, no changes");
+        assert_eq!(deadline_text(1), "Deadline reached, 1 change");
+        assert_eq!(deadline_text(3), "Deadline reached, 3 changes");
+    }
+}
@@ -0,0 +1,116 @@
+use std::time::Duration;
+use rtlola_interpreter::Value;
+use crate::error::RtlolaError;
+use crate::rtlola_monitor::RtlolaMonitor;
+use crate::verdict::VerdictReport;
+
+// The verdict or error of each monitor that received an event, by spec name
+pub type GroupVerdicts = Vec<(String, Result<VerdictReport, RtlolaError>)>;
+
+// Several independent monitors fed from the same named input stream
+#[derive(Default)]
+pub struct MonitorGroup {
+    monitors: Vec<(String, RtlolaMonitor)>,
+}
+
+impl MonitorGroup {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Add a monitor under a name identifying its spec in the combined verdicts
+    pub fn add(&mut self, spec_name: &str, monitor: RtlolaMonitor) {
+        self.monitors.push((spec_name.to_string(), monitor));
+    }
+
+    pub fn get(&self, spec_name: &str) -> Option<&RtlolaMonitor> {
+        self.monitors.iter().find(|(name, _)| name == spec_name).map(|(_, monitor)| monitor)
+    }
+
+    pub fn len(&self) -> usize {
+        self.monitors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.monitors.is_empty()
+    }
+
+    pub fn start(&mut self) -> Result<(), RtlolaError> {
+        self.monitors.iter_mut().try_for_each(|(_, monitor)| monitor.start())
+    }
+
+    // Route the inputs each monitor declares to it and collect each monitor's verdict or error in the order the
+    // monitors were added. Monitors that declare none of the given inputs receive no event. A failing monitor does
+    // not keep the others from processing the event. Without a time, the clock of the first monitor receiving the
+    // event is read once and every monitor processes the event at that time.
+    pub fn process_event(&mut self, named: &[(&str, Value)], current_time: Option<Duration>) -> Result<GroupVerdicts, RtlolaError> {
+        let relevant = self.monitors.iter()
+            .map(|(_, monitor)| {
+                named.iter()
+                    .filter(|(name, _)| monitor.input_names().iter().any(|n| n == name))
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let Some(first) = relevant.iter().position(|inputs| !inputs.is_empty()) else {
+            return Ok(Vec::new());
+        };
+        let time = self.monitors[first].1.resolve_time(current_time)?;
+
+        Ok(self.monitors.iter_mut()
+            .zip(relevant)
+            .filter(|(_, inputs)| !inputs.is_empty())
+            .map(|((spec_name, monitor), inputs)| (spec_name.clone(), monitor.process_named_event(&inputs, Some(time), None)))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use crate::rtlola_monitor::make_float;
+
+    fn group(clock: Option<MockClock>) -> MonitorGroup {
+        let mut group = MonitorGroup::new();
+        for (spec_name, spec, inputs) in [("speed", "input a: Float64\noutput fast := a > 10.0", &["a"][..]), ("sum", "input a: Float64\ninput b: Float64\noutput s := a + b", &["a", "b"][..])] {
+            let builder = RtlolaMonitor::builder("", inputs).spec_text(spec);
+            let builder = match &clock {
+                Some(clock) => builder.clock(clock.clone()),
+                None => builder,
+            };
+            group.add(spec_name, builder.build().unwrap());
+        }
+        group.start().unwrap();
+        group
+    }
+
+    fn event() -> [(&'static str, Value); 2] {
+        [("a", make_float(1.0).unwrap()), ("b", make_float(2.0).unwrap())]
+    }
+
+    #[test]
+    fn a_failing_monitor_does_not_stop_the_others() {
+        let mut group = group(None);
+        // Only sum receives the event at 5s, so the event at 3s goes back in time for sum alone
+        group.process_event(&[("b", make_float(1.0).unwrap())], Some(Duration::from_secs(5))).unwrap();
+        let results = group.process_event(&event(), Some(Duration::from_secs(3))).unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results[0].1.is_ok());
+        assert!(matches!(results[1].1, Err(RtlolaError::TimeRegression { .. })));
+
+        let results = group.process_event(&event(), Some(Duration::from_secs(6))).unwrap();
+        let seqs = results.into_iter().map(|(name, result)| (name, result.unwrap().seq)).collect::<Vec<_>>();
+        assert_eq!(seqs, [("speed".to_string(), Some(2)), ("sum".to_string(), Some(2))]);
+    }
+
+    #[test]
+    fn the_clock_is_read_once_per_event() {
+        let clock = MockClock::new();
+        clock.set(Duration::from_secs(2));
+        let mut group = group(Some(clock));
+        let times = group.process_event(&event(), None).unwrap().into_iter().map(|(_, result)| result.unwrap().time).collect::<Vec<_>>();
+        assert_eq!(times, [Duration::from_secs(2); 2]);
+        assert!(group.process_event(&[("c", make_float(1.0).unwrap())], None).unwrap().is_empty());
+    }
+}
@@ -1,12 +1,71 @@
-use std::convert::Infallible;
-use std::os::raw::{c_char, c_double, c_ulong, c_long, c_int, c_longlong, c_ulonglong, c_void};
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use std::os::raw::{c_char, c_double, c_longlong, c_ulonglong, c_void};
+use std::cell::Cell;
+use std::collections::BTreeSet;
+use std::sync::{Mutex, PoisonError};
 use std::ffi::{CStr, CString};
-use std::time::Instant;
 use std::result::Result::{Ok, Err};
-use ordered_float::NotNan;
-use rtlola_interpreter::Value;
-use rtlola_monitor::RtlolaMonitor;
-mod rtlola_monitor;
+use std::time::Duration;
+use rtlola_interpreter::monitor::Change;
+// Prints to stderr when the `stderr-log` feature is enabled, otherwise only evaluates its arguments
+macro_rules! log_error {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "stderr-log")]
+        eprintln!($($arg)*);
+        #[cfg(not(feature = "stderr-log"))]
+        let _ = format_args!($($arg)*);
+    }};
+}
+
+mod annotations;
+mod backend;
+mod background;
+pub mod builder;
+pub mod clock;
+pub mod config;
+mod csv;
+#[cfg(feature = "serde")]
+pub mod describe;
+pub mod error;
+pub mod format;
+pub mod group;
+#[cfg(feature = "serde")]
+pub mod ndjson;
+#[cfg(not(feature = "color"))]
+mod nocolor;
+pub mod rtlola_monitor;
+pub mod source;
+pub mod summary;
+#[cfg(test)]
+mod test_util;
+pub mod timeline;
+pub mod verdict;
+pub mod verify;
+
+// The safe Rust API. Rust users work with these types and never need the FFI structs below.
+pub use rtlola_interpreter::Value;
+pub use builder::RtlolaMonitorBuilder;
+pub use clock::{Clock, MockClock};
+pub use config::MonitorConfig;
+#[cfg(feature = "wall-clock")]
+pub use clock::SystemClock;
+#[cfg(feature = "serde")]
+pub use describe::{OutputDescription, SpecAnalysis, SpecDescription, StreamDescription, TriggerDescription};
+pub use error::RtlolaError;
+pub use format::{Color, ColorChoice, ColorTheme, EchoInputs, OutputFormat, VerdictStyle};
+pub use group::{GroupVerdicts, MonitorGroup};
+#[cfg(feature = "serde")]
+pub use ndjson::NdjsonSource;
+pub use rtlola_monitor::{
+    event_time, make_float, make_str, DuplicateTimePolicy, EdgeMode, InputTime, InputValidation, ModeCheck, MonitorMode, OutOfOrderPolicy,
+    OverflowPolicy, Pacing, PausePolicy, RtlolaMonitor,
+};
+pub use source::EventSource;
+pub use summary::{OutputSummary, RunSummary, WindowSummary};
+pub use timeline::{Series, Timeline, TriggerPoint};
+pub use verdict::{OutputChange, TriggerChange, VerdictReport};
+pub use verify::{verify_trace, TraceVerdict, TraceViolation};
 /*
 #[repr(C)]
 pub struct RTLolaMonitorHandle {
@@ -16,26 +75,129 @@ pub struct RTLolaMonitorHandle {
 }
 */
 
-#[repr(C)]
+// Opaque to C, which only passes the pointer around
 pub struct RTLolaMonitorHandle {
     inner: *mut c_void, // pointer to RtlolaMonitor
-    
+    trigger_messages: Vec<CString>, // owned by the handle, returned by rtlola_get_trigger_message
+    output_names: Vec<CString>, // owned by the handle, returned by rtlola_get_output_name
+    input_names: Vec<CString>, // owned by the handle, returned by rtlola_never_seen_input_name
+    value_string: CString, // string value last returned by rtlola_get_output_value_by_index
+    input_schema: CString, // returned by rtlola_input_schema
+    dependency_dot: CString, // returned by rtlola_dependency_dot
+    spec_source: Option<CString>, // returned by rtlola_spec_source
+    callbacks: Callbacks, // invoked by the background consumer
+}
+
+// Called for every trigger that fires. `message` and `source` are only valid during the call, `source` is NULL for unlabeled events.
+// NULL stands for no callback.
+pub type RTLolaTriggerCallback = Option<extern "C" fn(trigger_idx: u64, message: *const c_char, time: c_double, source: *const c_char, user_data: *mut c_void)>;
+
+// Called for every new output value. The strings are only valid during the call, `source` is NULL for unlabeled events.
+// NULL stands for no callback.
+pub type RTLolaOutputCallback = Option<extern "C" fn(name: *const c_char, value: *const c_char, time: c_double, source: *const c_char, user_data: *mut c_void)>;
+
+#[derive(Clone, Copy)]
+struct Callbacks {
+    trigger: RTLolaTriggerCallback,
+    trigger_data: *mut c_void,
+    output: RTLolaOutputCallback,
+    output_data: *mut c_void,
+}
+
+impl Default for Callbacks {
+    fn default() -> Self {
+        Callbacks { trigger: None, trigger_data: std::ptr::null_mut(), output: None, output_data: std::ptr::null_mut() }
+    }
+}
+
+// The callbacks run on the background thread, the caller guarantees that this is safe for user_data
+unsafe impl Send for Callbacks {}
+
+impl Callbacks {
+    fn dispatch(&self, report: &VerdictReport) {
+        let time = report.time.as_secs_f64();
+        let source = report.source.as_deref().map(|s| CString::new(s).unwrap_or_default());
+        let source_ptr = source.as_ref().map_or(std::ptr::null(), |s| s.as_ptr());
+        if let Some(callback) = self.trigger {
+            for (idx, val) in report.fired_triggers() {
+                let message = CString::new(val.to_string()).unwrap_or_default();
+                callback(idx as u64, message.as_ptr(), time, source_ptr, self.trigger_data);
+            }
+        }
+        if let Some(callback) = self.output {
+            for output in &report.outputs {
+                if let Change::Value(_, val) = &output.change {
+                    let name = CString::new(output.name.as_str()).unwrap_or_default();
+                    let value = CString::new(val.to_string()).unwrap_or_default();
+                    callback(name.as_ptr(), value.as_ptr(), time, source_ptr, self.output_data);
+                }
+            }
+        }
+    }
+}
+
+// Everything needed to create a monitor with a single call to rtlola_monitor_new_from_config
+#[repr(C)]
+pub struct RTLolaConfig {
+    pub spec_path: *const c_char, // Path to the specification, only read if spec_ptr is NULL
+    pub spec_ptr: *const u8, // Specification text of spec_len bytes, need not be null-terminated
+    pub spec_len: usize,
+    pub timeout_ms: u64,
+    pub input_names: *const *const c_char,
+    pub num_inputs: u64,
+    pub online: bool, // Create the monitor in online mode
+    pub nanos_time: bool, // Take offline event times as whole nanoseconds (InputTime::RelativeNanos)
+}
+
+// Type tag of an RTLolaInput or an output value, selecting the field of RTLolaValueData
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RTLolaValueType {
+    UInt64 = 0,
+    Int64 = 1,
+    Float64 = 2,
+    Bool = 3,
+    String = 4,
+}
+
+impl TryFrom<u32> for RTLolaValueType {
+    type Error = RtlolaError;
+
+    fn try_from(tag: u32) -> Result<Self, RtlolaError> {
+        match tag {
+            0 => Ok(RTLolaValueType::UInt64),
+            1 => Ok(RTLolaValueType::Int64),
+            2 => Ok(RTLolaValueType::Float64),
+            3 => Ok(RTLolaValueType::Bool),
+            4 => Ok(RTLolaValueType::String),
+            other => Err(RtlolaError::InvalidType(other)),
+        }
+    }
 }
 
 #[repr(C)]
 pub struct RTLolaInput {
-    name: *const c_char,
-    type_: u32, // 0=UInt64, 1=Int64, 2=Float64, 3=Bool, 4=String
-    value: RTLolaValueData,
+    pub name: *const c_char,
+    pub type_: u32, // An RTLolaValueType, kept as u32 so that unknown tags from C are an error instead of undefined behavior
+    pub value: RTLolaValueData,
 }
 
 #[repr(C)]
 pub union RTLolaValueData {
-    uint64_val: c_ulonglong,
-    int64_val: c_longlong,
-    float64_val: c_double,
-    bool_val: bool,
-    string_val: *const c_char,
+    pub uint64_val: c_ulonglong,
+    pub int64_val: c_longlong,
+    pub float64_val: c_double,
+    pub bool_val: bool,
+    pub string_val: *const c_char,
+}
+
+// The interpreter does not expose its version, keep this in sync with the rtlola-interpreter dependency in Cargo.toml
+const VERSION: &str = concat!("rtlola_integration ", env!("CARGO_PKG_VERSION"), " (interpreter 0.10.1)\0");
+
+// Versions of this library and the interpreter it was built with. The string is static and must not be freed.
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_version() -> *const c_char {
+    VERSION.as_ptr() as *const c_char
 }
 
 #[unsafe(no_mangle)]
@@ -44,59 +206,212 @@ pub extern "C" fn rtlola_monitor_new(
     timeout_ms: u64,
     input_names: *const *const c_char,
     num_inputs: u64
+) -> *mut RTLolaMonitorHandle {
+    new_handle(spec, timeout_ms, input_names, num_inputs, MonitorMode::Offline)
+}
+
+// Like rtlola_monitor_new, but the interpreter stamps events with the system time and evaluates timed streams in real time
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_monitor_new_online(
+    spec: *const c_char,
+    timeout_ms: u64,
+    input_names: *const *const c_char,
+    num_inputs: u64
+) -> *mut RTLolaMonitorHandle {
+    new_handle(spec, timeout_ms, input_names, num_inputs, MonitorMode::Online)
+}
+
+// Create a monitor from an RTLolaConfig. The spec is taken from spec_ptr/spec_len if spec_ptr is set,
+// otherwise from the file at spec_path. Returns NULL on failure.
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_monitor_new_from_config(config: *const RTLolaConfig) -> *mut RTLolaMonitorHandle {
+    if config.is_null() {
+        log_error!("Config pointer is null");
+        set_last_error(RTLOLA_NULL_ARGUMENT);
+        return std::ptr::null_mut();
+    }
+    let config = unsafe { &*config };
+
+    let builder = if !config.spec_ptr.is_null() {
+        let bytes = unsafe { std::slice::from_raw_parts(config.spec_ptr, config.spec_len) };
+        match std::str::from_utf8(bytes) {
+            Ok(spec) => RtlolaMonitor::builder("<inline>", &[]).spec_text(spec),
+            Err(e) => {
+                log_error!("Specification is not valid UTF-8: {}", e);
+                set_last_error(RTLOLA_INVALID_ARGUMENT);
+                return std::ptr::null_mut();
+            }
+        }
+    } else if config.spec_len != 0 {
+        log_error!("spec_len is {} but spec_ptr is null", config.spec_len);
+        set_last_error(RTLOLA_INVALID_ARGUMENT);
+        return std::ptr::null_mut();
+    } else if !config.spec_path.is_null() {
+        match unsafe { CStr::from_ptr(config.spec_path) }.to_str() {
+            Ok(path) => RtlolaMonitor::builder(path, &[]),
+            Err(e) => {
+                log_error!("Failed to parse specification path: {}", e);
+                set_last_error(RTLOLA_INVALID_ARGUMENT);
+                return std::ptr::null_mut();
+            }
+        }
+    } else {
+        log_error!("Config has neither spec_ptr nor spec_path");
+        set_last_error(RTLOLA_NULL_ARGUMENT);
+        return std::ptr::null_mut();
+    };
+
+    let Some(input_names) = c_input_names(config.input_names, config.num_inputs) else {
+        return std::ptr::null_mut();
+    };
+    let mode = if config.online { MonitorMode::Online } else { MonitorMode::Offline };
+    let input_time = if config.nanos_time { InputTime::RelativeNanos } else { InputTime::RelativeFloat };
+    match builder.input_names(&input_names).timeout_ms(config.timeout_ms).mode(mode).input_time(input_time).build() {
+        Ok(monitor) => into_handle(monitor),
+        Err(e) => {
+            log_error!("Failed to create monitor: {}", e);
+            set_last_error(e.code());
+            std::ptr::null_mut()
+        }
+    }
+}
+
+fn new_handle(
+    spec: *const c_char,
+    timeout_ms: u64,
+    input_names: *const *const c_char,
+    num_inputs: u64,
+    mode: MonitorMode,
 ) -> *mut RTLolaMonitorHandle {
     // Convert the C spec string to Rust String
     let spec_cstr = unsafe { CStr::from_ptr(spec) };
     let spec_str = match spec_cstr.to_str() {
         Ok(s) => s,
         Err(e) => {
-            eprintln!("Failed to parse specification string: {}", e);
+            log_error!("Failed to parse specification string: {}", e);
+            set_last_error(RTLOLA_INVALID_ARGUMENT);
             return std::ptr::null_mut();
         }
     };
 
-    // Convert the input names array
+    let Some(rust_input_names) = c_input_names(input_names, num_inputs) else {
+        return std::ptr::null_mut();
+    };
+
+    // Create the monitor instance
+    match RtlolaMonitor::with_mode(spec_str, timeout_ms, &rust_input_names, mode) {
+        Ok(monitor) => into_handle(monitor),
+        Err(e) => {
+            log_error!("Failed to create monitor: {}", e);
+            set_last_error(e.code());
+            std::ptr::null_mut()
+        }
+    }
+}
+
+// Convert the input names array, None after setting the last error if the array or a name is NULL or a name is
+// not valid UTF-8
+fn c_input_names<'a>(input_names: *const *const c_char, num_inputs: u64) -> Option<Vec<&'a str>> {
+    if num_inputs == 0 {
+        return Some(Vec::new());
+    }
+    if input_names.is_null() {
+        set_last_error(RTLOLA_NULL_ARGUMENT);
+        return None;
+    }
     let input_names_slice = unsafe { std::slice::from_raw_parts(input_names, num_inputs as usize) };
     let mut rust_input_names = Vec::with_capacity(num_inputs as usize);
-    
+
     for &name_ptr in input_names_slice {
+        if name_ptr.is_null() {
+            log_error!("Failed to parse input name: NULL");
+            set_last_error(RTLOLA_NULL_ARGUMENT);
+            return None;
+        }
         let name_cstr = unsafe { CStr::from_ptr(name_ptr) };
         match name_cstr.to_str() {
             Ok(s) => rust_input_names.push(s),
             Err(e) => {
-                eprintln!("Failed to parse input name: {}", e);
-                return std::ptr::null_mut();
+                log_error!("Failed to parse input name: {}", e);
+                set_last_error(RTLOLA_INVALID_ARGUMENT);
+                return None;
             }
         }
     }
+    Some(rust_input_names)
+}
 
-    // Create the monitor instance
-    let monitor = match RtlolaMonitor::new(spec_str, timeout_ms, &rust_input_names) {
-        Ok(m) => m,
-        Err(e) => {
-            eprintln!("Failed to create monitor: {}", e);
-            return std::ptr::null_mut();
-        }
-    };
+fn into_handle(monitor: RtlolaMonitor) -> *mut RTLolaMonitorHandle {
+    let trigger_messages = monitor.triggers()
+        .into_iter()
+        .map(|(_, msg)| CString::new(msg).unwrap_or_default())
+        .collect();
+
+    let output_names = monitor.outputs()
+        .into_iter()
+        .map(|name| CString::new(name).unwrap_or_default())
+        .collect();
+
+    let input_names = monitor.input_names()
+        .iter()
+        .map(|name| CString::new(name.as_str()).unwrap_or_default())
+        .collect();
+
+    let input_schema = CString::new(monitor.input_schema()).unwrap_or_default();
+    let dependency_dot = CString::new(monitor.dependency_dot()).unwrap_or_default();
+    let spec_source = monitor.spec_source().and_then(|spec| CString::new(spec).ok());
 
     // Box the monitor 
     let boxed_monitor = Box::new(monitor);
     
     // Create the handle with a pointer to the monitor
     let handle = Box::new(RTLolaMonitorHandle {
-        inner: Box::into_raw(boxed_monitor) as *mut c_void
+        inner: Box::into_raw(boxed_monitor) as *mut c_void,
+        trigger_messages,
+        output_names,
+        input_names,
+        value_string: CString::default(),
+        input_schema,
+        dependency_dot,
+        spec_source,
+        callbacks: Callbacks::default(),
     });
 
     // Return the raw pointer (caller now owns this)
-    Box::into_raw(handle)
+    let handle = Box::into_raw(handle);
+    LIVE_HANDLES.lock().unwrap_or_else(PoisonError::into_inner).insert(handle as usize);
+    handle
 }
 
+// Addresses of the handles created and not yet freed, see rtlola_monitor_free
+static LIVE_HANDLES: Mutex<BTreeSet<usize>> = Mutex::new(BTreeSet::new());
+
 #[unsafe(no_mangle)]
 pub extern "C" fn rtlola_process_inputs(
     handle: *mut RTLolaMonitorHandle,
     inputs: *const RTLolaInput,
     num_inputs: usize,
     time: c_double,
+) -> *mut c_char {
+    process_inputs(handle, inputs, num_inputs, event_time(time))
+}
+
+// Like rtlola_process_inputs with the time in whole nanoseconds, which reaches a RelativeNanos monitor unchanged
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_process_inputs_nanos(
+    handle: *mut RTLolaMonitorHandle,
+    inputs: *const RTLolaInput,
+    num_inputs: usize,
+    time_nanos: u64,
+) -> *mut c_char {
+    process_inputs(handle, inputs, num_inputs, Ok(Duration::from_nanos(time_nanos)))
+}
+
+fn process_inputs(
+    handle: *mut RTLolaMonitorHandle,
+    inputs: *const RTLolaInput,
+    num_inputs: usize,
+    time: Result<Duration, RtlolaError>,
 ) -> *mut c_char {
     // Null check inputs
     if handle.is_null() || inputs.is_null() {
@@ -104,29 +419,20 @@ pub extern "C" fn rtlola_process_inputs(
     }
 
     // Safe access to monitor 
-    let monitor = unsafe { &mut *((*(handle as *mut RTLolaMonitorHandle)).inner as *mut RtlolaMonitor) };
-    let inputs_slice = unsafe { std::slice::from_raw_parts(inputs, num_inputs) };
+    let monitor = unsafe { &mut *((*handle).inner as *mut RtlolaMonitor) };
 
     // Convert inputs to Values
-    let mut values = Vec::with_capacity(num_inputs);
-    for input in inputs_slice {
-        let value = match input.type_ {
-            0 => Value::Unsigned(unsafe { input.value.uint64_val }),
-            1 => Value::Signed(unsafe { input.value.int64_val }),
-            2 => Value::Float(NotNan::try_from(unsafe { input.value.float64_val }).unwrap_or_default()),
-            3 => Value::Bool(unsafe { input.value.bool_val }),
-            4 => {
-                let s = unsafe { CStr::from_ptr(input.value.string_val) };
-                Value::Str(s.to_string_lossy().into_owned().into())
-            },
-            _ => return std::ptr::null_mut(), // Invalid type
-        };
-        values.push(value);
-    }
+    let values = match event_inputs(monitor, inputs, num_inputs, false).and_then(|slice| convert_inputs(slice, monitor.input_validation())) {
+        Ok(values) => values,
+        Err(e) => {
+            log_error!("Failed to convert inputs: {}", e);
+            set_last_error(e.code());
+            return std::ptr::null_mut();
+        }
+    };
     
-    let currre_time = time as f64;
     // Process the event and get the result string
-    match monitor.process_event_verdict(values, Some(currre_time)) {
+    match time.and_then(|time| monitor.process_event_verdict_str(values, Some(time))) {
         Ok(output_str) => {
             // Convert Rust String to C-compatible string
             match CString::new(output_str) {
@@ -134,9 +440,9 @@ pub extern "C" fn rtlola_process_inputs(
                 Err(_) => std::ptr::null_mut(),
             }
         },
-        Err(err_str) => {
+        Err(err) => {
             // Convert error string to C-compatible string
-            match CString::new(err_str) {
+            match CString::new(err.to_string()) {
                 Ok(c_string) => c_string.into_raw(),
                 Err(_) => std::ptr::null_mut(),
             }
@@ -144,24 +450,1671 @@ pub extern "C" fn rtlola_process_inputs(
     }
 }
 
-// Function to free the string memory
+// Process an event whose values are given in parallel typed arrays instead of RTLolaInput structs.
+// Input i has type tag types[i] and its value at index i of the array for that type, e.g. f64s[i] for a Float64.
+// Arrays of types no input has may be NULL. Returns true if the event was processed; its report is available
+// like that of any other processed event.
 #[unsafe(no_mangle)]
-pub extern "C" fn rtlola_free_string(s: *mut c_char) {
-    if !s.is_null() {
-        unsafe { CString::from_raw(s) };
+pub extern "C" fn rtlola_process_columnar(
+    handle: *mut RTLolaMonitorHandle,
+    types: *const u32,
+    u64s: *const u64,
+    i64s: *const i64,
+    f64s: *const c_double,
+    bools: *const bool,
+    strs: *const *const c_char,
+    count: usize,
+    time: c_double,
+) -> bool {
+    if handle.is_null() || types.is_null() {
+        set_last_error(RTLOLA_NULL_ARGUMENT);
+        return false;
+    }
+
+    let monitor = unsafe { &mut *((*handle).inner as *mut RtlolaMonitor) };
+    let expected = monitor.input_names().len();
+    if count != expected {
+        let e = RtlolaError::InputCount { expected, got: count };
+        log_error!("Failed to process columnar event: {}", e);
+        set_last_error(e.code());
+        return false;
+    }
+    let types = unsafe { std::slice::from_raw_parts(types, count) };
+    let columns = Columns { u64s, i64s, f64s, bools, strs };
+    if let Some(position) = types.iter().position(|&tag| columns.missing(tag)) {
+        log_error!("Failed to process columnar event: no array for the type of input {}", position);
+        set_last_error(RTLOLA_NULL_ARGUMENT);
+        return false;
+    }
+
+    let result = types.iter()
+        .enumerate()
+        .map(|(i, &tag)| columns.value(tag, i, monitor.input_validation()))
+        .collect::<Result<Vec<_>, _>>()
+        .and_then(|values| monitor.process_event(values, Some(event_time(time)?)));
+    match result {
+        Ok(_) => true,
+        Err(e) => {
+            log_error!("Failed to process columnar event: {}", e);
+            set_last_error(e.code());
+            false
+        }
+    }
+}
+
+// The typed value arrays of rtlola_process_columnar
+struct Columns {
+    u64s: *const u64,
+    i64s: *const i64,
+    f64s: *const c_double,
+    bools: *const bool,
+    strs: *const *const c_char,
+}
+
+impl Columns {
+    // Whether the array for a known type tag is NULL. Unknown tags are reported by value.
+    fn missing(&self, tag: u32) -> bool {
+        match RTLolaValueType::try_from(tag) {
+            Ok(RTLolaValueType::UInt64) => self.u64s.is_null(),
+            Ok(RTLolaValueType::Int64) => self.i64s.is_null(),
+            Ok(RTLolaValueType::Float64) => self.f64s.is_null(),
+            Ok(RTLolaValueType::Bool) => self.bools.is_null(),
+            Ok(RTLolaValueType::String) => self.strs.is_null(),
+            Err(_) => false,
+        }
+    }
+
+    // Read input i from the array of its type, which must not be NULL
+    fn value(&self, tag: u32, i: usize, validation: InputValidation) -> Result<Value, RtlolaError> {
+        let value = match RTLolaValueType::try_from(tag)? {
+            RTLolaValueType::UInt64 => Value::Unsigned(unsafe { *self.u64s.add(i) }),
+            RTLolaValueType::Int64 => Value::Signed(unsafe { *self.i64s.add(i) }),
+            RTLolaValueType::Float64 => make_float(unsafe { *self.f64s.add(i) })?,
+            RTLolaValueType::Bool => Value::Bool(unsafe { *self.bools.add(i) }),
+            RTLolaValueType::String => {
+                let ptr = unsafe { *self.strs.add(i) };
+                if ptr.is_null() {
+                    return Err(RtlolaError::NullString(i));
+                }
+                make_str(unsafe { CStr::from_ptr(ptr) }.to_bytes(), i, validation)?
+            },
+        };
+        Ok(value)
     }
 }
 
+// Check a complete trace against a specification, see verify_trace. `spec` is the specification text,
+// `inputs` holds `num_events * num_inputs` entries, one row per event, and `times` one timestamp per event.
+// Returns 1 if no trigger fired, 0 if one did, otherwise the negative code of the error, which also becomes
+// rtlola_last_error_code. The number of fired triggers is written to `violations_out` unless it is NULL.
 #[unsafe(no_mangle)]
-pub extern "C" fn rtlola_monitor_start(handle: *mut RTLolaMonitorHandle) -> bool {
-    let handle = unsafe { &mut *handle };
-    let monitor = unsafe { &mut *(handle.inner as *mut RtlolaMonitor) };
-    monitor.start().is_ok()
+pub extern "C" fn rtlola_verify_trace(
+    spec: *const c_char,
+    input_names: *const *const c_char,
+    num_inputs: u64,
+    inputs: *const RTLolaInput,
+    times: *const c_double,
+    num_events: usize,
+    violations_out: *mut u64,
+) -> i32 {
+    if spec.is_null() || (num_events > 0 && (inputs.is_null() || times.is_null())) {
+        set_last_error(RTLOLA_NULL_ARGUMENT);
+        return RTLOLA_NULL_ARGUMENT;
+    }
+    let Ok(spec) = unsafe { CStr::from_ptr(spec) }.to_str() else {
+        log_error!("Failed to verify trace: the specification is not valid UTF-8");
+        set_last_error(RTLOLA_INVALID_ARGUMENT);
+        return RTLOLA_INVALID_ARGUMENT;
+    };
+    let Some(names) = c_input_names(input_names, num_inputs) else {
+        return rtlola_last_error_code();
+    };
+    let row = num_inputs as usize;
+    let Some(total) = num_events.checked_mul(row) else {
+        log_error!("Failed to verify trace: {} events of {} inputs overflow", num_events, row);
+        set_last_error(RTLOLA_INVALID_ARGUMENT);
+        return RTLOLA_INVALID_ARGUMENT;
+    };
+
+    let events = if num_events == 0 {
+        Ok(Vec::new())
+    } else {
+        let inputs = unsafe { std::slice::from_raw_parts(inputs, total) };
+        let times = unsafe { std::slice::from_raw_parts(times, num_events) };
+        times.iter()
+            .enumerate()
+            .map(|(i, &time)| convert_inputs(&inputs[i * row..(i + 1) * row], InputValidation::Lossy).map(|values| (values, time)))
+            .collect::<Result<Vec<_>, _>>()
+    };
+    match events.and_then(|events| verify_trace(spec, &names, events)) {
+        Ok(verdict) => {
+            if !violations_out.is_null() {
+                unsafe { *violations_out = verdict.violations.len() as u64 };
+            }
+            i32::from(verdict.passed)
+        },
+        Err(e) => {
+            log_error!("Failed to verify trace: {}", e);
+            set_last_error(e.code());
+            e.code()
+        }
+    }
 }
 
+// Process a batch of events, continuing past events that fail.
+// `inputs` holds `num_events * inputs_per_event` entries, one row per event, and `times` one timestamp per event.
+// Indices of failed events are written to `failed_out` (up to `failed_capacity`), their total number to `failed_count_out`.
+// Returns the number of events processed successfully.
 #[unsafe(no_mangle)]
-pub extern "C" fn rtlola_monitor_free(handle: *mut RTLolaMonitorHandle) {
-    if !handle.is_null() {
-        unsafe { Box::from_raw(handle) };
+pub extern "C" fn rtlola_process_batch(
+    handle: *mut RTLolaMonitorHandle,
+    inputs: *const RTLolaInput,
+    inputs_per_event: usize,
+    times: *const c_double,
+    num_events: usize,
+    failed_out: *mut u64,
+    failed_capacity: usize,
+    failed_count_out: *mut usize,
+) -> u64 {
+    if handle.is_null() || inputs.is_null() || times.is_null() {
+        return 0;
+    }
+
+    let monitor = unsafe { &mut *((*handle).inner as *mut RtlolaMonitor) };
+    let expected = monitor.input_names().len();
+    let total = match num_events.checked_mul(inputs_per_event) {
+        Some(total) if inputs_per_event == expected && inputs_per_event > 0 => total,
+        _ => {
+            let e = RtlolaError::InputCount { expected, got: inputs_per_event };
+            log_error!("Failed to process batch: {}", e);
+            set_last_error(e.code());
+            return 0;
+        },
+    };
+    let inputs_slice = unsafe { std::slice::from_raw_parts(inputs, total) };
+    let times_slice = unsafe { std::slice::from_raw_parts(times, num_events) };
+
+    let mut indices = Vec::with_capacity(num_events);
+    let mut events = Vec::with_capacity(num_events);
+    let mut failed = Vec::new();
+    for (i, (row, &time)) in inputs_slice.chunks(inputs_per_event).zip(times_slice).enumerate() {
+        match convert_inputs(row, monitor.input_validation()) {
+            Ok(values) => {
+                indices.push(i as u64);
+                events.push((values, time));
+            },
+            Err(e) => {
+                log_error!("Event {} failed: {}", i, e);
+                failed.push(i as u64);
+            },
+        }
+    }
+
+    let results = monitor.process_trace_resilient(events);
+    let mut succeeded = 0;
+    for (i, result) in indices.into_iter().zip(results) {
+        match result {
+            Ok(_) => succeeded += 1,
+            Err(_) => failed.push(i),
+        }
+    }
+    failed.sort_unstable();
+
+    if !failed_out.is_null() {
+        let n = failed.len().min(failed_capacity);
+        unsafe { std::ptr::copy_nonoverlapping(failed.as_ptr(), failed_out, n) };
+    }
+    if !failed_count_out.is_null() {
+        unsafe { *failed_count_out = failed.len() };
+    }
+    succeeded
+}
+
+// Process an event and write whether any trigger fired to `out`.
+// Returns 0 on success, otherwise the negative code of the error.
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_process_violated(
+    handle: *mut RTLolaMonitorHandle,
+    inputs: *const RTLolaInput,
+    count: usize,
+    time: c_double,
+    out: *mut bool,
+) -> i32 {
+    if handle.is_null() || inputs.is_null() || out.is_null() {
+        return RTLOLA_NULL_ARGUMENT;
+    }
+    let monitor = unsafe { &mut *((*handle).inner as *mut RtlolaMonitor) };
+
+    let result = event_inputs(monitor, inputs, count, false)
+        .and_then(|slice| convert_inputs(slice, monitor.input_validation()))
+        .and_then(|values| monitor.process_event_violated(values, Some(event_time(time)?)));
+    match result {
+        Ok(violated) => {
+            unsafe { *out = violated };
+            0
+        },
+        Err(e) => {
+            log_error!("Failed to process event: {}", e);
+            set_last_error(e.code());
+            e.code()
+        }
+    }
+}
+
+// Process an event and write the indices of the fired triggers to `triggers_out`, at most `max` of them.
+// `count_out` receives the number of fired triggers, which may exceed `max`.
+// Returns 0 on success, otherwise the negative code of the error.
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_process_triggers(
+    handle: *mut RTLolaMonitorHandle,
+    inputs: *const RTLolaInput,
+    num_inputs: usize,
+    time: c_double,
+    triggers_out: *mut u64,
+    max: usize,
+    count_out: *mut usize,
+) -> i32 {
+    if handle.is_null() || inputs.is_null() || (triggers_out.is_null() && max > 0) {
+        return RTLOLA_NULL_ARGUMENT;
+    }
+    let monitor = unsafe { &mut *((*handle).inner as *mut RtlolaMonitor) };
+
+    let result = event_inputs(monitor, inputs, num_inputs, false)
+        .and_then(|slice| convert_inputs(slice, monitor.input_validation()))
+        .and_then(|values| monitor.process_event_triggers(values, Some(event_time(time)?)));
+    let fired = match result {
+        Ok(fired) => fired,
+        Err(e) => {
+            log_error!("Failed to process event: {}", e);
+            set_last_error(e.code());
+            return e.code();
+        }
+    };
+
+    for (i, (idx, _)) in fired.iter().take(max).enumerate() {
+        unsafe { *triggers_out.add(i) = *idx as u64 };
+    }
+    if !count_out.is_null() {
+        unsafe { *count_out = fired.len() };
+    }
+    0
+}
+
+// The inputs of an event as a slice. `count` comes from C and is checked against the monitor's input names
+// before the slice is built, so a wrong count is rejected instead of reading past the caller's array.
+// Named events may list a subset of the inputs.
+fn event_inputs<'a>(monitor: &RtlolaMonitor, inputs: *const RTLolaInput, count: usize, subset: bool) -> Result<&'a [RTLolaInput], RtlolaError> {
+    let expected = monitor.input_names().len();
+    if count > expected || (!subset && count < expected) {
+        return Err(RtlolaError::InputCount { expected, got: count });
+    }
+    Ok(unsafe { std::slice::from_raw_parts(inputs, count) })
+}
+
+// Convert C inputs to Values, see rtlola_input_to_value
+fn convert_inputs(inputs: &[RTLolaInput], validation: InputValidation) -> Result<Vec<Value>, RtlolaError> {
+    inputs.iter()
+        .enumerate()
+        .map(|(i, input)| rtlola_input_to_value(input, i, validation))
+        .collect()
+}
+
+// Convert one C input to a Value, failing on unknown type tags, NaN floats, NULL strings and, if strict,
+// invalid UTF-8. `position` is the input's position in the event, reported on error.
+fn rtlola_input_to_value(input: &RTLolaInput, position: usize, validation: InputValidation) -> Result<Value, RtlolaError> {
+    let value = match RTLolaValueType::try_from(input.type_)? {
+        RTLolaValueType::UInt64 => Value::Unsigned(unsafe { input.value.uint64_val }),
+        RTLolaValueType::Int64 => Value::Signed(unsafe { input.value.int64_val }),
+        RTLolaValueType::Float64 => make_float(unsafe { input.value.float64_val })?,
+        RTLolaValueType::Bool => Value::Bool(unsafe { input.value.bool_val }),
+        RTLolaValueType::String => {
+            let ptr = unsafe { input.value.string_val };
+            if ptr.is_null() {
+                return Err(RtlolaError::NullString(position));
+            }
+            make_str(unsafe { CStr::from_ptr(ptr) }.to_bytes(), position, validation)?
+        },
+    };
+    Ok(value)
+}
+
+// Reject every event from the first one with a time after ms milliseconds on
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_set_max_duration(handle: *mut RTLolaMonitorHandle, ms: u64) {
+    if handle.is_null() {
+        return;
+    }
+    let monitor = unsafe { &mut *((*handle).inner as *mut RtlolaMonitor) };
+    monitor.set_max_duration(Duration::from_millis(ms));
+}
+
+// Reject string inputs that are not valid UTF-8 instead of replacing invalid sequences
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_set_strict_utf8(handle: *mut RTLolaMonitorHandle, strict: bool) {
+    if handle.is_null() {
+        return;
+    }
+    let monitor = unsafe { &mut *((*handle).inner as *mut RtlolaMonitor) };
+    monitor.set_input_validation(if strict { InputValidation::StrictUtf8 } else { InputValidation::Lossy });
+}
+
+// Which inputs verdict strings echo: 0 = all (default), 1 = only changed values, 2 = none.
+// Returns false for any other value.
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_set_echo_inputs(handle: *mut RTLolaMonitorHandle, echo: u32) -> bool {
+    if handle.is_null() {
+        return false;
+    }
+    let echo = match echo {
+        0 => EchoInputs::All,
+        1 => EchoInputs::Changed,
+        2 => EchoInputs::None,
+        _ => return false,
+    };
+    let monitor = unsafe { &mut *((*handle).inner as *mut RtlolaMonitor) };
+    monitor.set_echo_inputs(echo);
+    true
+}
+
+// Whether timed verdict strings list the latest inputs under the rtlola_set_echo_inputs setting, off by default
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_set_echo_timed_inputs(handle: *mut RTLolaMonitorHandle, echo: bool) {
+    if handle.is_null() {
+        return;
+    }
+    let monitor = unsafe { &mut *((*handle).inner as *mut RtlolaMonitor) };
+    monitor.set_echo_timed_inputs(echo);
+}
+
+// Decimals of floats in verdict strings, 6 by default
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_set_precision(handle: *mut RTLolaMonitorHandle, precision: usize) {
+    if handle.is_null() {
+        return;
+    }
+    let monitor = unsafe { &mut *((*handle).inner as *mut RtlolaMonitor) };
+    monitor.set_precision(precision);
+}
+
+// Run RtlolaMonitor::self_test, returns true if the spec produced a verdict for a default-valued event
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_monitor_self_test(handle: *const RTLolaMonitorHandle) -> bool {
+    if handle.is_null() {
+        return false;
+    }
+    let monitor = unsafe { &*((*handle).inner as *const RtlolaMonitor) };
+    match monitor.self_test() {
+        Ok(()) => true,
+        Err(e) => {
+            log_error!("Self test failed: {}", e);
+            set_last_error(e.code());
+            false
+        }
+    }
+}
+
+// Append `unit` (e.g. " m/s") to the values of the named output in verdict strings.
+// Returns false if the spec has no output of that name.
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_set_output_unit(handle: *mut RTLolaMonitorHandle, name: *const c_char, unit: *const c_char) -> bool {
+    if handle.is_null() || name.is_null() || unit.is_null() {
+        return false;
+    }
+    let monitor = unsafe { &mut *((*handle).inner as *mut RtlolaMonitor) };
+    let name = unsafe { CStr::from_ptr(name) }.to_string_lossy();
+    let unit = unsafe { CStr::from_ptr(unit) }.to_string_lossy();
+    match monitor.set_output_unit(&name, &unit) {
+        Ok(()) => true,
+        Err(e) => {
+            log_error!("Failed to set output unit: {}", e);
+            set_last_error(e.code());
+            false
+        }
     }
-}
\ No newline at end of file
+}
+
+// Apply `value * scale + offset` to the float values of the named input, see RtlolaMonitor::set_input_transform
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_set_input_transform(
+    handle: *mut RTLolaMonitorHandle,
+    name: *const c_char,
+    scale: c_double,
+    offset: c_double,
+) -> bool {
+    if handle.is_null() || name.is_null() {
+        set_last_error(RTLOLA_NULL_ARGUMENT);
+        return false;
+    }
+    let monitor = unsafe { &mut *((*handle).inner as *mut RtlolaMonitor) };
+    let name = unsafe { CStr::from_ptr(name) }.to_string_lossy();
+    match monitor.set_input_transform(&name, scale, offset) {
+        Ok(()) => true,
+        Err(e) => {
+            log_error!("Failed to set input transform: {}", e);
+            set_last_error(e.code());
+            false
+        }
+    }
+}
+
+// Error code returned when a required pointer argument is NULL
+pub const RTLOLA_NULL_ARGUMENT: i32 = -100;
+// Error code of a failed call whose string argument is not valid UTF-8 or whose arguments are inconsistent
+pub const RTLOLA_INVALID_ARGUMENT: i32 = -101;
+
+thread_local! {
+    static LAST_ERROR: Cell<i32> = const { Cell::new(0) };
+}
+
+fn set_last_error(code: i32) {
+    LAST_ERROR.with(|last| last.set(code));
+}
+
+// Error code of the most recent failed call on the calling thread, 0 if no call failed yet. Successful calls do not
+// reset it. Use it to find out why a function returned NULL or false, e.g. RtlolaError::SpecRead (-1) for a missing
+// specification file versus RtlolaError::Parse (-2) for an invalid specification.
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_last_error_code() -> i32 {
+    LAST_ERROR.with(Cell::get)
+}
+
+// Validate an event against the spec's input count and types without processing it.
+// Returns 0 if the event is valid, otherwise the negative code of the error.
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_check_event(
+    handle: *const RTLolaMonitorHandle,
+    inputs: *const RTLolaInput,
+    count: usize,
+) -> i32 {
+    if handle.is_null() || inputs.is_null() {
+        return RTLOLA_NULL_ARGUMENT;
+    }
+    let monitor = unsafe { &*((*handle).inner as *const RtlolaMonitor) };
+
+    let result = event_inputs(monitor, inputs, count, false)
+        .and_then(|slice| convert_inputs(slice, monitor.input_validation()))
+        .and_then(|values| monitor.check_event(&values));
+    match result {
+        Ok(()) => 0,
+        Err(e) => e.code(),
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_get_trigger_count(handle: *const RTLolaMonitorHandle) -> u64 {
+    if handle.is_null() {
+        return 0;
+    }
+    let handle = unsafe { &*handle };
+    handle.trigger_messages.len() as u64
+}
+
+// How many triggers have not fired in this run yet, see RtlolaMonitor::dormant_triggers
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_dormant_trigger_count(handle: *const RTLolaMonitorHandle) -> u64 {
+    if handle.is_null() {
+        return 0;
+    }
+    let monitor = unsafe { &*((*handle).inner as *const RtlolaMonitor) };
+    monitor.dormant_triggers().len() as u64
+}
+
+// How many triggers fired at least once in this run, see RtlolaMonitor::fired_triggers
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_fired_trigger_count(handle: *const RTLolaMonitorHandle) -> u64 {
+    if handle.is_null() {
+        return 0;
+    }
+    let monitor = unsafe { &*((*handle).inner as *const RtlolaMonitor) };
+    monitor.fired_triggers().len() as u64
+}
+
+// The message of trigger `idx`. The string is owned by the handle and must not be freed.
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_get_trigger_message(handle: *const RTLolaMonitorHandle, idx: u64) -> *const c_char {
+    if handle.is_null() {
+        return std::ptr::null();
+    }
+    let handle = unsafe { &*handle };
+    match handle.trigger_messages.get(idx as usize) {
+        Some(msg) => msg.as_ptr(),
+        None => std::ptr::null(),
+    }
+}
+
+// Advance the monitor to `time` seconds without new input values, see RtlolaMonitor::tick.
+// Returns the timed verdicts that became ready, formatted like rtlola_process_inputs and empty if there are none,
+// or NULL on error. The caller frees the string with rtlola_free_string.
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_tick(handle: *mut RTLolaMonitorHandle, time: c_double) -> *mut c_char {
+    if handle.is_null() {
+        set_last_error(RTLOLA_NULL_ARGUMENT);
+        return std::ptr::null_mut();
+    }
+    let monitor = unsafe { &mut *((*handle).inner as *mut RtlolaMonitor) };
+    match monitor.tick_str(time) {
+        Ok(verdicts) => CString::new(verdicts).map_or(std::ptr::null_mut(), CString::into_raw),
+        Err(e) => {
+            log_error!("Failed to tick: {}", e);
+            set_last_error(e.code());
+            std::ptr::null_mut()
+        }
+    }
+}
+
+// Number of input names that have not received a value yet, see RtlolaMonitor::never_seen_inputs
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_never_seen_inputs_count(handle: *const RTLolaMonitorHandle) -> usize {
+    if handle.is_null() {
+        return 0;
+    }
+    let monitor = unsafe { &*((*handle).inner as *const RtlolaMonitor) };
+    monitor.never_seen_inputs().len()
+}
+
+// Name of the idx-th input that has not received a value yet, NULL if idx is out of range. The string is owned by the handle.
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_never_seen_input_name(handle: *const RTLolaMonitorHandle, idx: usize) -> *const c_char {
+    if handle.is_null() {
+        return std::ptr::null();
+    }
+    let handle = unsafe { &*handle };
+    let monitor = unsafe { &*(handle.inner as *const RtlolaMonitor) };
+    let Some(name) = monitor.never_seen_inputs().into_iter().nth(idx) else {
+        return std::ptr::null();
+    };
+    match monitor.input_names().iter().position(|n| *n == name) {
+        Some(pos) => handle.input_names[pos].as_ptr(),
+        None => std::ptr::null(),
+    }
+}
+
+// Sequence number of the latest accepted event, counting from 1, 0 before the first event
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_last_event_seq(handle: *const RTLolaMonitorHandle) -> u64 {
+    if handle.is_null() {
+        return 0;
+    }
+    let monitor = unsafe { &*((*handle).inner as *const RtlolaMonitor) };
+    monitor.last_event_seq()
+}
+
+// Keep fewer than limit verdicts pending when an event is submitted, 0 (the default) removes the limit.
+// When the limit is reached, submitting waits for the background consumer, or discards the oldest pending
+// verdicts if drop_oldest is set.
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_set_verdict_limit(handle: *mut RTLolaMonitorHandle, limit: u64, drop_oldest: bool) {
+    if handle.is_null() {
+        return;
+    }
+    let policy = if drop_oldest { OverflowPolicy::DropOldest } else { OverflowPolicy::Block };
+    let monitor = unsafe { &mut *((*handle).inner as *mut RtlolaMonitor) };
+    monitor.set_verdict_limit(limit as usize, policy);
+}
+
+// Verdicts discarded because the verdict limit was reached, see RtlolaMonitor::dropped_verdicts
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_dropped_verdicts(handle: *const RTLolaMonitorHandle) -> u64 {
+    if handle.is_null() {
+        return 0;
+    }
+    let monitor = unsafe { &*((*handle).inner as *const RtlolaMonitor) };
+    monitor.dropped_verdicts()
+}
+
+// Whether trigger idx fired in the latest verdict that evaluated it, see RtlolaMonitor::is_trigger_active
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_is_trigger_active(handle: *const RTLolaMonitorHandle, idx: u64) -> bool {
+    if handle.is_null() {
+        return false;
+    }
+    let monitor = unsafe { &*((*handle).inner as *const RtlolaMonitor) };
+    monitor.is_trigger_active(idx as usize)
+}
+
+// The spec's inputs as a comma-separated name:type list. The string is owned by the handle and must not be freed.
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_input_schema(handle: *const RTLolaMonitorHandle) -> *const c_char {
+    if handle.is_null() {
+        return std::ptr::null();
+    }
+    let handle = unsafe { &*handle };
+    handle.input_schema.as_ptr()
+}
+
+// The specification source the monitor was built from, owned by the handle.
+// NULL if the monitor was built from a parsed MIR or the source contains a NUL byte.
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_spec_source(handle: *const RTLolaMonitorHandle) -> *const c_char {
+    if handle.is_null() {
+        return std::ptr::null();
+    }
+    let handle = unsafe { &*handle };
+    handle.spec_source.as_ref().map_or(std::ptr::null(), |spec| spec.as_ptr())
+}
+
+// The stream dependency graph in Graphviz DOT format, owned by the handle
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_dependency_dot(handle: *const RTLolaMonitorHandle) -> *const c_char {
+    if handle.is_null() {
+        return std::ptr::null();
+    }
+    let handle = unsafe { &*handle };
+    handle.dependency_dot.as_ptr()
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_get_output_count(handle: *const RTLolaMonitorHandle) -> u64 {
+    if handle.is_null() {
+        return 0;
+    }
+    let handle = unsafe { &*handle };
+    handle.output_names.len() as u64
+}
+
+// The name of output `idx`, NULL if `idx` is out of range. The string is owned by the handle and must not be freed.
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_get_output_name(handle: *const RTLolaMonitorHandle, idx: u64) -> *const c_char {
+    if handle.is_null() {
+        return std::ptr::null();
+    }
+    let handle = unsafe { &*handle };
+    match handle.output_names.get(idx as usize) {
+        Some(name) => name.as_ptr(),
+        None => std::ptr::null(),
+    }
+}
+
+// Write the latest value of output `idx` and its type tag. Returns false if `idx` is out of range, the output has
+// no value yet or its value has no C representation (tuples, bytes). A string value stays valid until the next call.
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_get_output_value_by_index(
+    handle: *mut RTLolaMonitorHandle,
+    idx: u64,
+    out: *mut RTLolaValueData,
+    type_out: *mut u32,
+) -> bool {
+    if handle.is_null() || out.is_null() || type_out.is_null() {
+        return false;
+    }
+    let handle = unsafe { &mut *handle };
+    let Some(name) = handle.output_names.get(idx as usize) else {
+        return false;
+    };
+    let monitor = unsafe { &*(handle.inner as *const RtlolaMonitor) };
+    let Some(value) = monitor.last_output_value(&name.to_string_lossy()) else {
+        return false;
+    };
+
+    let (data, type_) = match value {
+        Value::Unsigned(u) => (RTLolaValueData { uint64_val: u }, RTLolaValueType::UInt64),
+        Value::Signed(i) => (RTLolaValueData { int64_val: i }, RTLolaValueType::Int64),
+        Value::Float(f) => (RTLolaValueData { float64_val: f.into_inner() }, RTLolaValueType::Float64),
+        Value::Bool(b) => (RTLolaValueData { bool_val: b }, RTLolaValueType::Bool),
+        Value::Str(s) => {
+            handle.value_string = CString::new(s.as_bytes()).unwrap_or_default();
+            (RTLolaValueData { string_val: handle.value_string.as_ptr() }, RTLolaValueType::String)
+        },
+        Value::None | Value::Tuple(_) | Value::Bytes(_) => return false,
+    };
+    unsafe {
+        *out = data;
+        *type_out = type_ as u32;
+    }
+    true
+}
+
+// Look up the held value of a named output, see RtlolaMonitor::current_output
+fn current_output(handle: *const RTLolaMonitorHandle, name: *const c_char) -> Option<Value> {
+    if handle.is_null() || name.is_null() {
+        return None;
+    }
+    let monitor = unsafe { &*((*handle).inner as *const RtlolaMonitor) };
+    let name = unsafe { CStr::from_ptr(name) }.to_str().ok()?;
+    monitor.current_output(name)
+}
+
+// Typed accessors for the value a named output holds, see RtlolaMonitor::current_output. Each writes the value to
+// `out` and returns true if the output holds a value of that type, otherwise `out` is left unchanged.
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_current_output_float(handle: *const RTLolaMonitorHandle, name: *const c_char, out: *mut f64) -> bool {
+    match current_output(handle, name) {
+        Some(Value::Float(f)) if !out.is_null() => {
+            unsafe { *out = f.into_inner() };
+            true
+        },
+        _ => false,
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_current_output_int(handle: *const RTLolaMonitorHandle, name: *const c_char, out: *mut i64) -> bool {
+    match current_output(handle, name) {
+        Some(Value::Signed(i)) if !out.is_null() => {
+            unsafe { *out = i };
+            true
+        },
+        _ => false,
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_current_output_uint(handle: *const RTLolaMonitorHandle, name: *const c_char, out: *mut u64) -> bool {
+    match current_output(handle, name) {
+        Some(Value::Unsigned(u)) if !out.is_null() => {
+            unsafe { *out = u };
+            true
+        },
+        _ => false,
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_current_output_bool(handle: *const RTLolaMonitorHandle, name: *const c_char, out: *mut bool) -> bool {
+    match current_output(handle, name) {
+        Some(Value::Bool(b)) if !out.is_null() => {
+            unsafe { *out = b };
+            true
+        },
+        _ => false,
+    }
+}
+
+// The string an output holds, NULL if it holds no string. The caller frees it with rtlola_free_string.
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_current_output_str(handle: *const RTLolaMonitorHandle, name: *const c_char) -> *mut c_char {
+    match current_output(handle, name) {
+        Some(Value::Str(s)) => CString::new(s.as_bytes()).map_or(std::ptr::null_mut(), CString::into_raw),
+        _ => std::ptr::null_mut(),
+    }
+}
+
+// Register the trigger callback used by rtlola_monitor_run_background, NULL removes it
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_set_trigger_callback(
+    handle: *mut RTLolaMonitorHandle,
+    callback: RTLolaTriggerCallback,
+    user_data: *mut c_void,
+) {
+    if handle.is_null() {
+        return;
+    }
+    let handle = unsafe { &mut *handle };
+    handle.callbacks.trigger = callback;
+    handle.callbacks.trigger_data = user_data;
+}
+
+// Register the output callback used by rtlola_monitor_run_background, NULL removes it
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_set_output_callback(
+    handle: *mut RTLolaMonitorHandle,
+    callback: RTLolaOutputCallback,
+    user_data: *mut c_void,
+) {
+    if handle.is_null() {
+        return;
+    }
+    let handle = unsafe { &mut *handle };
+    handle.callbacks.output = callback;
+    handle.callbacks.output_data = user_data;
+}
+
+// Only report the `count` listed outputs and triggers (named trigger_<index>) in verdict strings.
+// A count of 0 reports all streams again. Returns false if a name is not a stream of the spec.
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_set_output_filter(
+    handle: *mut RTLolaMonitorHandle,
+    names: *const *const c_char,
+    count: usize,
+) -> bool {
+    if handle.is_null() || (names.is_null() && count > 0) {
+        return false;
+    }
+    let monitor = unsafe { &mut *((*handle).inner as *mut RtlolaMonitor) };
+    let names_slice = if count == 0 { &[] } else { unsafe { std::slice::from_raw_parts(names, count) } };
+    let names = names_slice.iter()
+        .map(|&name| unsafe { CStr::from_ptr(name) }.to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    let names = names.iter().map(String::as_str).collect::<Vec<_>>();
+
+    match monitor.set_output_filter(&names) {
+        Ok(()) => true,
+        Err(e) => {
+            log_error!("Failed to set output filter: {}", e);
+            set_last_error(e.code());
+            false
+        }
+    }
+}
+
+// When a boolean output appears in verdict strings: 0 = every value (default), 1 = only when it turns true,
+// 2 = only when it turns false, 3 = whenever it changes. Returns false for an unknown output or any other mode.
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_set_output_edge_mode(handle: *mut RTLolaMonitorHandle, name: *const c_char, mode: u32) -> bool {
+    if handle.is_null() || name.is_null() {
+        return false;
+    }
+    let mode = match mode {
+        0 => EdgeMode::Level,
+        1 => EdgeMode::RisingEdge,
+        2 => EdgeMode::FallingEdge,
+        3 => EdgeMode::BothEdges,
+        _ => return false,
+    };
+    let monitor = unsafe { &mut *((*handle).inner as *mut RtlolaMonitor) };
+    let name = unsafe { CStr::from_ptr(name) }.to_string_lossy();
+    match monitor.set_output_edge_mode(&name, mode) {
+        Ok(()) => true,
+        Err(e) => {
+            log_error!("Failed to set output edge mode: {}", e);
+            set_last_error(e.code());
+            false
+        }
+    }
+}
+
+// Start delivering verdicts to the registered callbacks from a background thread.
+// Callbacks registered afterwards only take effect on the next start.
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_monitor_run_background(handle: *mut RTLolaMonitorHandle) -> bool {
+    if handle.is_null() {
+        return false;
+    }
+    let handle = unsafe { &mut *handle };
+    let monitor = unsafe { &mut *(handle.inner as *mut RtlolaMonitor) };
+    let callbacks = handle.callbacks;
+    match monitor.run_background(move |report| callbacks.dispatch(&report)) {
+        Ok(()) => true,
+        Err(e) => {
+            log_error!("Failed to start background consumer: {}", e);
+            set_last_error(e.code());
+            false
+        }
+    }
+}
+
+// Stop the background consumer. Blocks until queued verdicts are delivered and the thread ended.
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_monitor_stop(handle: *mut RTLolaMonitorHandle) -> bool {
+    if handle.is_null() {
+        return false;
+    }
+    let monitor = unsafe { &mut *((*handle).inner as *mut RtlolaMonitor) };
+    monitor.stop_background()
+}
+
+// Hand an event to the monitor without waiting for its verdict, for use with rtlola_monitor_run_background
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_submit_inputs(
+    handle: *mut RTLolaMonitorHandle,
+    inputs: *const RTLolaInput,
+    num_inputs: usize,
+    time: c_double,
+) -> bool {
+    if handle.is_null() || inputs.is_null() {
+        return false;
+    }
+    let monitor = unsafe { &mut *((*handle).inner as *mut RtlolaMonitor) };
+
+    let result = event_inputs(monitor, inputs, num_inputs, false)
+        .and_then(|slice| convert_inputs(slice, monitor.input_validation()))
+        .and_then(|values| monitor.submit_event(values, Some(event_time(time)?)));
+    match result {
+        Ok(()) => true,
+        Err(e) => {
+            log_error!("Failed to submit event: {}", e);
+            set_last_error(e.code());
+            false
+        }
+    }
+}
+
+// The spec description as JSON, see RtlolaMonitor::describe_json.
+// The caller frees the string with rtlola_free_string.
+#[cfg(feature = "serde")]
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_describe_json(handle: *const RTLolaMonitorHandle) -> *mut c_char {
+    if handle.is_null() {
+        return std::ptr::null_mut();
+    }
+    let monitor = unsafe { &*((*handle).inner as *const RtlolaMonitor) };
+    match CString::new(monitor.describe_json()) {
+        Ok(c_string) => c_string.into_raw(),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+// The recent verdicts as JSON, see RtlolaMonitor::history_json.
+// The caller frees the string with rtlola_free_string.
+#[cfg(feature = "serde")]
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_history_json(handle: *const RTLolaMonitorHandle) -> *mut c_char {
+    if handle.is_null() {
+        return std::ptr::null_mut();
+    }
+    let monitor = unsafe { &*((*handle).inner as *const RtlolaMonitor) };
+    match CString::new(monitor.history_json()) {
+        Ok(c_string) => c_string.into_raw(),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+// The recorded run as timeline JSON, see RtlolaMonitor::timeline_json.
+// The caller frees the string with rtlola_free_string.
+#[cfg(feature = "serde")]
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_timeline_json(handle: *const RTLolaMonitorHandle) -> *mut c_char {
+    if handle.is_null() {
+        return std::ptr::null_mut();
+    }
+    let monitor = unsafe { &*((*handle).inner as *const RtlolaMonitor) };
+    match CString::new(monitor.timeline_json()) {
+        Ok(c_string) => c_string.into_raw(),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+// Wait up to retries more times for a verdict that did not arrive within the timeout, see RtlolaMonitor::set_recv_retries
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_set_recv_retries(handle: *mut RTLolaMonitorHandle, retries: u32) {
+    if handle.is_null() {
+        return;
+    }
+    let monitor = unsafe { &mut *((*handle).inner as *mut RtlolaMonitor) };
+    monitor.set_recv_retries(retries);
+}
+
+// Keep the last len verdicts for rtlola_history_json, 0 (the default) keeps none
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_set_history_len(handle: *mut RTLolaMonitorHandle, len: u64) {
+    if handle.is_null() {
+        return;
+    }
+    let monitor = unsafe { &mut *((*handle).inner as *mut RtlolaMonitor) };
+    monitor.set_history_len(len as usize);
+}
+
+// End the run and return its RunSummary as JSON, see RtlolaMonitor::finalize.
+// The handle is freed and must not be used afterwards, NULL is returned for a handle that is not live.
+// The caller frees the string with rtlola_free_string.
+#[cfg(feature = "serde")]
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_monitor_finalize_json(handle: *mut RTLolaMonitorHandle) -> *mut c_char {
+    if handle.is_null() {
+        return std::ptr::null_mut();
+    }
+    let Some(monitor) = release_handle(handle) else {
+        return std::ptr::null_mut();
+    };
+    let summary = monitor.finalize();
+    match serde_json::to_string(&summary).map(CString::new) {
+        Ok(Ok(c_string)) => c_string.into_raw(),
+        Ok(Err(_)) => std::ptr::null_mut(),
+        Err(e) => {
+            log_error!("Failed to serialize run summary: {}", e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+// The alive instances of a parameterized output as a JSON array of {"params": [...], "value": ...} objects,
+// see RtlolaMonitor::active_instances. The caller frees the string with rtlola_free_string.
+#[cfg(feature = "serde")]
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_active_instances_json(handle: *const RTLolaMonitorHandle, output_name: *const c_char) -> *mut c_char {
+    if handle.is_null() || output_name.is_null() {
+        return std::ptr::null_mut();
+    }
+    let monitor = unsafe { &*((*handle).inner as *const RtlolaMonitor) };
+    let output_name = unsafe { CStr::from_ptr(output_name) }.to_string_lossy();
+    let instances = monitor.active_instances(&output_name)
+        .into_iter()
+        .map(|(params, value)| serde_json::json!({ "params": params, "value": value }))
+        .collect::<Vec<_>>();
+    match CString::new(serde_json::Value::Array(instances).to_string()) {
+        Ok(c_string) => c_string.into_raw(),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+// Like rtlola_submit_inputs, but inputs are matched to streams by their `name` and may be a subset.
+// `source` labels the event's origin in the callbacks and may be NULL.
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_submit_named_inputs(
+    handle: *mut RTLolaMonitorHandle,
+    inputs: *const RTLolaInput,
+    num_inputs: usize,
+    time: c_double,
+    source: *const c_char,
+) -> bool {
+    if handle.is_null() || inputs.is_null() {
+        set_last_error(RTLOLA_NULL_ARGUMENT);
+        return false;
+    }
+    let monitor = unsafe { &mut *((*handle).inner as *mut RtlolaMonitor) };
+    let source = if source.is_null() {
+        None
+    } else {
+        Some(unsafe { CStr::from_ptr(source) }.to_string_lossy().into_owned())
+    };
+
+    let inputs_slice = match event_inputs(monitor, inputs, num_inputs, true) {
+        Ok(inputs_slice) => inputs_slice,
+        Err(e) => {
+            log_error!("Failed to submit event: {}", e);
+            set_last_error(e.code());
+            return false;
+        },
+    };
+    if let Some(position) = inputs_slice.iter().position(|input| input.name.is_null()) {
+        log_error!("Failed to submit event: name of input {} is NULL", position);
+        set_last_error(RTLOLA_NULL_ARGUMENT);
+        return false;
+    }
+
+    let result = convert_inputs(inputs_slice, monitor.input_validation()).and_then(|values| {
+        let names = inputs_slice.iter()
+            .map(|input| unsafe { CStr::from_ptr(input.name) }.to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        let named = names.iter().map(String::as_str).zip(values).collect::<Vec<_>>();
+        monitor.submit_named_event(&named, Some(event_time(time)?), source.as_deref())
+    });
+    match result {
+        Ok(()) => true,
+        Err(e) => {
+            log_error!("Failed to submit event: {}", e);
+            set_last_error(e.code());
+            false
+        }
+    }
+}
+
+// Function to free the string memory
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_free_string(s: *mut c_char) {
+    if !s.is_null() {
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_monitor_start(handle: *mut RTLolaMonitorHandle) -> bool {
+    let handle = unsafe { &mut *handle };
+    let monitor = unsafe { &mut *(handle.inner as *mut RtlolaMonitor) };
+    monitor.start().is_ok()
+}
+
+// Give the monitor a fresh interpreter and start it, see RtlolaMonitor::restart. Use it when rtlola_monitor_start
+// failed. The run starts over, registered callbacks and settings are kept.
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_monitor_restart(handle: *mut RTLolaMonitorHandle) -> bool {
+    if handle.is_null() {
+        set_last_error(RTLOLA_NULL_ARGUMENT);
+        return false;
+    }
+    let monitor = unsafe { &mut *((*handle).inner as *mut RtlolaMonitor) };
+    match monitor.restart() {
+        Ok(()) => true,
+        Err(e) => {
+            log_error!("Failed to restart monitor: {}", e);
+            set_last_error(e.code());
+            false
+        }
+    }
+}
+
+// Stop accepting events, see RtlolaMonitor::pause. Until rtlola_monitor_resume, processing an event fails with
+// RtlolaError::Paused (-25). With buffer set the event is kept and processed on resume, otherwise it is discarded.
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_monitor_pause(handle: *mut RTLolaMonitorHandle, buffer: bool) {
+    if handle.is_null() {
+        return;
+    }
+    let monitor = unsafe { &mut *((*handle).inner as *mut RtlolaMonitor) };
+    monitor.set_pause_policy(if buffer { PausePolicy::Buffer } else { PausePolicy::Reject });
+    monitor.pause();
+}
+
+// Accept events again and process the buffered ones, see RtlolaMonitor::resume. Their verdicts reach the callbacks,
+// the CSV sink and the history, or rtlola_monitor_run_background's callbacks while it runs. Returns false if a
+// buffered event failed, that event and the ones after it stay buffered.
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_monitor_resume(handle: *mut RTLolaMonitorHandle) -> bool {
+    if handle.is_null() {
+        set_last_error(RTLOLA_NULL_ARGUMENT);
+        return false;
+    }
+    let monitor = unsafe { &mut *((*handle).inner as *mut RtlolaMonitor) };
+    match monitor.resume() {
+        Ok(_) => true,
+        Err(e) => {
+            log_error!("Failed to process buffered event: {}", e);
+            set_last_error(e.code());
+            false
+        }
+    }
+}
+
+// Whether rtlola_monitor_free ends the run and logs the verdicts nobody consumed, see RtlolaMonitor::set_flush_on_drop
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_set_flush_on_drop(handle: *mut RTLolaMonitorHandle, flush: bool) {
+    if handle.is_null() {
+        return;
+    }
+    let monitor = unsafe { &mut *((*handle).inner as *mut RtlolaMonitor) };
+    monitor.set_flush_on_drop(flush);
+}
+
+// Free a handle and the monitor it owns. NULL and handles that were already freed are ignored.
+#[unsafe(no_mangle)]
+pub extern "C" fn rtlola_monitor_free(handle: *mut RTLolaMonitorHandle) {
+    if handle.is_null() {
+        return;
+    }
+    drop(release_handle(handle));
+}
+
+// Free a handle and return the monitor it owns, None if the handle is not live.
+// A freed handle cannot be inspected, so double frees are detected by the registry of live handles.
+fn release_handle(handle: *mut RTLolaMonitorHandle) -> Option<Box<RtlolaMonitor>> {
+    if !LIVE_HANDLES.lock().unwrap_or_else(PoisonError::into_inner).remove(&(handle as usize)) {
+        log_error!("Ignoring release of a handle that is not live: {:p}", handle);
+        return None;
+    }
+    let handle = unsafe { Box::from_raw(handle) };
+    (!handle.inner.is_null()).then(|| unsafe { Box::from_raw(handle.inner as *mut RtlolaMonitor) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{BALL_INPUTS, BALL_SPEC};
+
+    // Owned C strings of the ball spec's input names, alive while their pointers are used
+    fn ball_names() -> [CString; 3] {
+        BALL_INPUTS.map(|name| CString::new(name).unwrap())
+    }
+
+    fn started_ball_handle(timeout_ms: u64) -> *mut RTLolaMonitorHandle {
+        let names = ball_names();
+        let name_ptrs = names.iter().map(|name| name.as_ptr()).collect::<Vec<_>>();
+        let spec = CString::new(BALL_SPEC).unwrap();
+        let handle = rtlola_monitor_new(spec.as_ptr(), timeout_ms, name_ptrs.as_ptr(), 3);
+        assert!(!handle.is_null());
+        assert!(rtlola_monitor_start(handle));
+        handle
+    }
+
+    fn ball_handle() -> *mut RTLolaMonitorHandle {
+        started_ball_handle(500)
+    }
+
+    fn float_input(x: f64) -> RTLolaInput {
+        RTLolaInput { name: std::ptr::null(), type_: RTLolaValueType::Float64 as u32, value: RTLolaValueData { float64_val: x } }
+    }
+
+    fn ball_inputs(height: f64, velocity: f64, temperature: f64) -> [RTLolaInput; 3] {
+        [float_input(height), float_input(velocity), float_input(temperature)]
+    }
+
+    #[test]
+    fn batch_skips_events_with_invalid_times() {
+        let handle = ball_handle();
+        let inputs: [_; 3] = std::array::from_fn(|_| ball_inputs(1.0, 0.0, 20.0));
+        let times = [1.0, -1.0, 2.0];
+        let mut failed = [0u64; 3];
+        let mut failed_count = 0;
+        let processed = rtlola_process_batch(handle, inputs.as_flattened().as_ptr(), 3, times.as_ptr(), 3, failed.as_mut_ptr(), 3, &mut failed_count);
+        assert_eq!(processed, 2);
+        assert_eq!((failed_count, failed[0]), (1, 1));
+        rtlola_monitor_free(handle);
+    }
+
+    #[test]
+    fn batch_rejects_events_without_inputs() {
+        let handle = ball_handle();
+        let inputs = ball_inputs(1.0, 0.0, 20.0);
+        let processed = rtlola_process_batch(handle, inputs.as_ptr(), 0, [1.0].as_ptr(), 1, std::ptr::null_mut(), 0, std::ptr::null_mut());
+        assert_eq!(processed, 0);
+        assert_eq!(rtlola_last_error_code(), -4);
+        rtlola_monitor_free(handle);
+    }
+
+    #[test]
+    fn process_inputs_reports_invalid_times() {
+        let handle = ball_handle();
+        let inputs = ball_inputs(1.0, 0.0, 20.0);
+        let message = rtlola_process_inputs(handle, inputs.as_ptr(), 3, f64::NAN);
+        let text = unsafe { CStr::from_ptr(message) }.to_str().unwrap().to_string();
+        rtlola_free_string(message);
+        assert_eq!(text, RtlolaError::InvalidTime(f64::NAN).to_string());
+        rtlola_monitor_free(handle);
+    }
+
+    #[test]
+    fn columnar_rejects_invalid_times() {
+        let handle = ball_handle();
+        let types = [RTLolaValueType::Float64 as u32; 3];
+        let f64s = [1.0, 0.0, 20.0];
+        let process = |time| {
+            rtlola_process_columnar(handle, types.as_ptr(), std::ptr::null(), std::ptr::null(), f64s.as_ptr(), std::ptr::null(), std::ptr::null(), 3, time)
+        };
+        assert!(!process(f64::INFINITY));
+        assert_eq!(rtlola_last_error_code(), -28);
+        assert!(process(1.0));
+        rtlola_monitor_free(handle);
+    }
+
+    #[test]
+    fn violated_rejects_invalid_times() {
+        let handle = ball_handle();
+        let inputs = ball_inputs(1.0, -1.0, 20.0);
+        let mut violated = false;
+        assert_eq!(rtlola_process_violated(handle, inputs.as_ptr(), 3, -2.0, &mut violated), -28);
+        assert_eq!(rtlola_process_violated(handle, inputs.as_ptr(), 3, 2.0, &mut violated), 0);
+        // The ball is falling
+        assert!(violated);
+        rtlola_monitor_free(handle);
+    }
+
+    #[test]
+    fn triggers_reject_invalid_times() {
+        let handle = ball_handle();
+        let inputs = ball_inputs(0.0, 0.0, 20.0);
+        let mut triggers = [0u64; 5];
+        let mut count = 0;
+        assert_eq!(rtlola_process_triggers(handle, inputs.as_ptr(), 3, f64::NAN, triggers.as_mut_ptr(), 5, &mut count), -28);
+        assert_eq!(rtlola_process_triggers(handle, inputs.as_ptr(), 3, 1.0, triggers.as_mut_ptr(), 5, &mut count), 0);
+        // Close to and on the ground
+        assert_eq!(triggers[..count], [2, 3]);
+        rtlola_monitor_free(handle);
+    }
+
+    #[test]
+    fn submit_rejects_invalid_times() {
+        let handle = ball_handle();
+        let inputs = ball_inputs(1.0, 0.0, 20.0);
+        assert!(!rtlola_submit_inputs(handle, inputs.as_ptr(), 3, -1.0));
+        assert_eq!(rtlola_last_error_code(), -28);
+        assert!(rtlola_submit_inputs(handle, inputs.as_ptr(), 3, 1.0));
+        rtlola_monitor_free(handle);
+    }
+
+    #[test]
+    fn submit_named_rejects_invalid_times() {
+        let handle = ball_handle();
+        let inputs = [RTLolaInput { name: c"height".as_ptr(), ..float_input(1.0) }];
+        assert!(!rtlola_submit_named_inputs(handle, inputs.as_ptr(), 1, f64::INFINITY, std::ptr::null()));
+        assert_eq!(rtlola_last_error_code(), -28);
+        assert!(rtlola_submit_named_inputs(handle, inputs.as_ptr(), 1, 1.0, c"sensor".as_ptr()));
+        rtlola_monitor_free(handle);
+    }
+
+    #[test]
+    fn verify_trace_reports_errors_through_the_last_error() {
+        let spec = c"input a: Float64\ntrigger a > 10.0 \"too high\"";
+        let names = [c"a".as_ptr()];
+        let inputs = [float_input(1.0), float_input(20.0)];
+        let times = [1.0, 2.0];
+        let mut violations = 0;
+        assert_eq!(rtlola_verify_trace(spec.as_ptr(), names.as_ptr(), 1, inputs.as_ptr(), times.as_ptr(), 2, &mut violations), 0);
+        assert_eq!(violations, 1);
+
+        assert_eq!(rtlola_verify_trace(spec.as_ptr(), std::ptr::null(), 1, inputs.as_ptr(), times.as_ptr(), 2, &mut violations), RTLOLA_NULL_ARGUMENT);
+        assert_eq!(rtlola_last_error_code(), RTLOLA_NULL_ARGUMENT);
+        let null_names = [std::ptr::null()];
+        assert_eq!(rtlola_verify_trace(spec.as_ptr(), null_names.as_ptr(), 1, inputs.as_ptr(), times.as_ptr(), 2, &mut violations), RTLOLA_NULL_ARGUMENT);
+
+        let code = rtlola_verify_trace(c"input a: Float64\noutput".as_ptr(), names.as_ptr(), 1, inputs.as_ptr(), times.as_ptr(), 2, &mut violations);
+        assert_eq!(code, RtlolaError::Parse(String::new()).code());
+        assert_eq!(rtlola_last_error_code(), code);
+    }
+
+    #[test]
+    fn submit_named_rejects_null_names() {
+        let handle = ball_handle();
+        let inputs = [RTLolaInput { name: c"height".as_ptr(), ..float_input(1.0) }, float_input(0.0)];
+        assert!(!rtlola_submit_named_inputs(handle, inputs.as_ptr(), 2, 1.0, std::ptr::null()));
+        assert_eq!(rtlola_last_error_code(), RTLOLA_NULL_ARGUMENT);
+        // The named input alone is accepted
+        assert!(rtlola_submit_named_inputs(handle, inputs.as_ptr(), 1, 1.0, std::ptr::null()));
+        rtlola_monitor_free(handle);
+    }
+
+    #[test]
+    fn process_inputs_nanos_keeps_integer_times() {
+        let names = ball_names();
+        let name_ptrs = names.iter().map(|name| name.as_ptr()).collect::<Vec<_>>();
+        let spec = CString::new(BALL_SPEC).unwrap();
+        let config = RTLolaConfig {
+            spec_path: spec.as_ptr(),
+            spec_ptr: std::ptr::null(),
+            spec_len: 0,
+            timeout_ms: 500,
+            input_names: name_ptrs.as_ptr(),
+            num_inputs: 3,
+            online: false,
+            nanos_time: true,
+        };
+        let handle = rtlola_monitor_new_from_config(&config);
+        assert!(rtlola_monitor_start(handle));
+        let inputs = ball_inputs(1.0, 0.0, 20.0);
+        let verdict = rtlola_process_inputs_nanos(handle, inputs.as_ptr(), 3, 9_007_199_254_740_993);
+        let text = unsafe { CStr::from_ptr(verdict) }.to_str().unwrap().to_string();
+        rtlola_free_string(verdict);
+        // 2^53 + 1 nanoseconds, which a double cannot represent
+        assert!(text.starts_with("[9007199.254740993s] Processing new event"), "{}", text);
+        rtlola_monitor_free(handle);
+    }
+
+    #[test]
+    fn unknown_type_tags_are_rejected() {
+        let handle = ball_handle();
+        let inputs = [float_input(1.0), float_input(0.0), RTLolaInput { type_: 9, ..float_input(20.0) }];
+        assert!(rtlola_process_inputs(handle, inputs.as_ptr(), 3, 1.0).is_null());
+        assert_eq!(rtlola_last_error_code(), RtlolaError::InvalidType(9).code());
+        rtlola_monitor_free(handle);
+    }
+
+    fn tagged(tag: RTLolaValueType, value: RTLolaValueData) -> RTLolaInput {
+        RTLolaInput { name: std::ptr::null(), type_: tag as u32, value }
+    }
+
+    fn to_value(input: &RTLolaInput) -> Result<Value, RtlolaError> {
+        rtlola_input_to_value(input, 0, InputValidation::Lossy)
+    }
+
+    #[test]
+    fn uint64_inputs_convert_to_unsigned() {
+        let input = tagged(RTLolaValueType::UInt64, RTLolaValueData { uint64_val: u64::MAX });
+        assert_eq!(to_value(&input).unwrap(), Value::Unsigned(u64::MAX));
+    }
+
+    #[test]
+    fn int64_inputs_convert_to_signed() {
+        let input = tagged(RTLolaValueType::Int64, RTLolaValueData { int64_val: -7 });
+        assert_eq!(to_value(&input).unwrap(), Value::Signed(-7));
+    }
+
+    #[test]
+    fn float64_inputs_convert_to_float_and_reject_nan() {
+        assert_eq!(to_value(&float_input(2.5)).unwrap(), make_float(2.5).unwrap());
+        assert!(matches!(to_value(&float_input(f64::NAN)), Err(RtlolaError::NanFloat)));
+    }
+
+    #[test]
+    fn bool_inputs_convert_to_bool() {
+        let input = tagged(RTLolaValueType::Bool, RTLolaValueData { bool_val: true });
+        assert_eq!(to_value(&input).unwrap(), Value::Bool(true));
+    }
+
+    #[test]
+    fn string_inputs_convert_to_str() {
+        let input = tagged(RTLolaValueType::String, RTLolaValueData { string_val: c"ok".as_ptr() });
+        assert_eq!(to_value(&input).unwrap(), Value::Str("ok".into()));
+
+        let input = tagged(RTLolaValueType::String, RTLolaValueData { string_val: std::ptr::null() });
+        assert!(matches!(rtlola_input_to_value(&input, 2, InputValidation::Lossy), Err(RtlolaError::NullString(2))));
+
+        let invalid = tagged(RTLolaValueType::String, RTLolaValueData { string_val: c"\xff".as_ptr() });
+        assert_eq!(to_value(&invalid).unwrap(), Value::Str("\u{fffd}".into()));
+        assert!(matches!(rtlola_input_to_value(&invalid, 1, InputValidation::StrictUtf8), Err(RtlolaError::InvalidUtf8(1))));
+    }
+
+    #[test]
+    fn unknown_tags_do_not_convert() {
+        let input = RTLolaInput { type_: 5, ..float_input(1.0) };
+        assert!(matches!(to_value(&input), Err(RtlolaError::InvalidType(5))));
+    }
+
+    #[test]
+    fn pause_and_resume_through_the_c_api() {
+        let handle = ball_handle();
+        rtlola_monitor_pause(handle, true);
+        let inputs = ball_inputs(1.0, -1.0, 20.0);
+        assert!(!rtlola_submit_inputs(handle, inputs.as_ptr(), 3, 1.0));
+        assert_eq!(rtlola_last_error_code(), -25);
+        assert!(rtlola_tick(handle, 2.0).is_null());
+        assert_eq!(rtlola_last_error_code(), -25);
+
+        assert!(rtlola_monitor_resume(handle));
+        assert_eq!(rtlola_last_event_seq(handle), 1);
+        assert!(rtlola_is_trigger_active(handle, 0));
+        rtlola_monitor_free(handle);
+    }
+
+    #[test]
+    fn invalid_utf8_strings_under_both_validations() {
+        let names = [c"status".as_ptr(), c"code".as_ptr()];
+        let handle = rtlola_monitor_new(c"src/status_spec.lola".as_ptr(), 500, names.as_ptr(), 2);
+        assert!(rtlola_monitor_start(handle));
+        let inputs = [
+            RTLolaInput { name: std::ptr::null(), type_: RTLolaValueType::String as u32, value: RTLolaValueData { string_val: c"ok\xff".as_ptr() } },
+            RTLolaInput { name: std::ptr::null(), type_: RTLolaValueType::UInt64 as u32, value: RTLolaValueData { uint64_val: 200 } },
+        ];
+
+        let verdict = rtlola_process_inputs(handle, inputs.as_ptr(), 2, 1.0);
+        let text = unsafe { CStr::from_ptr(verdict) }.to_str().unwrap().to_string();
+        rtlola_free_string(verdict);
+        assert!(text.contains("ok\u{fffd}"), "{}", text);
+
+        rtlola_set_strict_utf8(handle, true);
+        assert!(rtlola_process_inputs(handle, inputs.as_ptr(), 2, 2.0).is_null());
+        assert_eq!(rtlola_last_error_code(), RtlolaError::InvalidUtf8(0).code());
+        rtlola_monitor_free(handle);
+    }
+
+    #[test]
+    fn output_index_accessors_check_the_range() {
+        let handle = ball_handle();
+        let count = rtlola_get_output_count(handle);
+        assert_eq!(count, 5);
+        assert!(!rtlola_get_output_name(handle, count - 1).is_null());
+        assert!(rtlola_get_output_name(handle, count).is_null());
+        assert!(rtlola_get_output_name(handle, u64::MAX).is_null());
+        assert!(rtlola_get_trigger_message(handle, rtlola_get_trigger_count(handle)).is_null());
+
+        let inputs = ball_inputs(1.0, -1.0, 20.0);
+        rtlola_free_string(rtlola_process_inputs(handle, inputs.as_ptr(), 3, 1.0));
+        let mut value = RTLolaValueData { uint64_val: 0 };
+        let mut type_ = u32::MAX;
+        assert!(rtlola_get_output_value_by_index(handle, 0, &mut value, &mut type_));
+        assert_eq!(type_, RTLolaValueType::Bool as u32);
+        // is_falling
+        assert!(unsafe { value.bool_val });
+        type_ = u32::MAX;
+        assert!(!rtlola_get_output_value_by_index(handle, count, &mut value, &mut type_));
+        assert!(!rtlola_get_output_value_by_index(handle, u64::MAX, &mut value, &mut type_));
+        assert_eq!(type_, u32::MAX);
+        rtlola_monitor_free(handle);
+    }
+
+    #[test]
+    fn input_schema_is_owned_by_the_handle() {
+        let handle = ball_handle();
+        let schema = unsafe { CStr::from_ptr(rtlola_input_schema(handle)) };
+        assert_eq!(schema.to_str().unwrap(), "height:Float64,velocity:Float64,temperature:Float64");
+        assert!(rtlola_input_schema(std::ptr::null()).is_null());
+        rtlola_monitor_free(handle);
+    }
+
+
+    #[test]
+    fn zero_timeout_handle_processes_a_trace() {
+        let handle = started_ball_handle(0);
+        for i in 1..=20 {
+            let inputs = ball_inputs(1.0, -1.0, 20.0);
+            let verdict = rtlola_process_inputs(handle, inputs.as_ptr(), 3, i as f64 * 0.1);
+            assert!(!verdict.is_null(), "event {}", i);
+            rtlola_free_string(verdict);
+        }
+        rtlola_monitor_free(handle);
+    }
+
+
+    #[test]
+    fn missing_and_invalid_specs_have_distinct_codes() {
+        let names = [c"x".as_ptr()];
+        let handle = rtlola_monitor_new(c"does/not/exist.lola".as_ptr(), 500, names.as_ptr(), 1);
+        assert!(handle.is_null());
+        assert_eq!(rtlola_last_error_code(), -1);
+
+        let mut spec = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut spec, b"input x: Float64\noutput y := x +").unwrap();
+        let path = CString::new(spec.path().to_str().unwrap()).unwrap();
+        let handle = rtlola_monitor_new(path.as_ptr(), 500, names.as_ptr(), 1);
+        assert!(handle.is_null());
+        assert_eq!(rtlola_last_error_code(), -2);
+    }
+
+
+    #[test]
+    fn never_seen_inputs_through_the_handle() {
+        let handle = ball_handle();
+        assert_eq!(rtlola_never_seen_inputs_count(handle), 3);
+        let name = rtlola_never_seen_input_name(handle, 1);
+        assert_eq!(unsafe { CStr::from_ptr(name) }, c"velocity");
+        assert!(rtlola_never_seen_input_name(handle, 3).is_null());
+        // Every event from C carries a value for each input
+        let inputs = ball_inputs(1.0, 0.0, 20.0);
+        rtlola_free_string(rtlola_process_inputs(handle, inputs.as_ptr(), 3, 1.0));
+        assert_eq!(rtlola_never_seen_inputs_count(handle), 0);
+        assert!(rtlola_never_seen_input_name(handle, 0).is_null());
+        rtlola_monitor_free(handle);
+    }
+
+
+    #[test]
+    fn wrong_input_counts_are_rejected_before_reading() {
+        let handle = ball_handle();
+        let inputs = ball_inputs(1.0, 0.0, 20.0);
+        // Counts past the end of the array would read out of bounds if they were trusted
+        assert!(rtlola_process_inputs(handle, inputs.as_ptr(), usize::MAX, 1.0).is_null());
+        assert_eq!(rtlola_last_error_code(), -4);
+        assert_eq!(rtlola_check_event(handle, inputs.as_ptr(), 4), -4);
+        assert_eq!(rtlola_check_event(handle, inputs.as_ptr(), 2), -4);
+        let processed = rtlola_process_batch(handle, inputs.as_ptr(), usize::MAX, [1.0, 2.0].as_ptr(), 2, std::ptr::null_mut(), 0, std::ptr::null_mut());
+        assert_eq!(processed, 0);
+        assert_eq!(rtlola_last_error_code(), -4);
+        // The matching count is accepted
+        assert_eq!(rtlola_check_event(handle, inputs.as_ptr(), 3), 0);
+        rtlola_monitor_free(handle);
+    }
+
+
+    #[test]
+    fn restart_through_the_handle() {
+        assert!(!rtlola_monitor_restart(std::ptr::null_mut()));
+        assert_eq!(rtlola_last_error_code(), RTLOLA_NULL_ARGUMENT);
+        let handle = ball_handle();
+        // A second start fails without affecting the monitor, a restart starts the run over
+        assert!(!rtlola_monitor_start(handle));
+        assert!(rtlola_monitor_restart(handle));
+        let inputs = ball_inputs(1.0, -1.0, 20.0);
+        let verdict = rtlola_process_inputs(handle, inputs.as_ptr(), 3, 1.0);
+        assert!(!verdict.is_null());
+        rtlola_free_string(verdict);
+        rtlola_monitor_free(handle);
+    }
+
+
+    #[test]
+    fn input_transform_through_the_handle() {
+        let handle = ball_handle();
+        assert!(rtlola_set_input_transform(handle, c"temperature".as_ptr(), 0.1, 2.0));
+        assert!(!rtlola_set_input_transform(handle, c"pressure".as_ptr(), 0.1, 2.0));
+        assert_eq!(rtlola_last_error_code(), RtlolaError::UnknownInput(String::new()).code());
+        assert!(!rtlola_set_input_transform(handle, std::ptr::null(), 0.1, 2.0));
+        // 100 reaches the monitor as 12.0, which hotInFar converts to 53.6
+        let inputs = ball_inputs(1.0, 0.0, 100.0);
+        rtlola_free_string(rtlola_process_inputs(handle, inputs.as_ptr(), 3, 1.0));
+        let monitor = unsafe { &*((*handle).inner as *const RtlolaMonitor) };
+        assert_eq!(monitor.current_output("hotInFar"), Some(make_float(12.0 * 9.0 / 5.0 + 32.0).unwrap()));
+        rtlola_monitor_free(handle);
+    }
+
+
+    #[test]
+    fn recv_retries_through_the_handle() {
+        rtlola_set_recv_retries(std::ptr::null_mut(), 3);
+        let handle = ball_handle();
+        rtlola_set_recv_retries(handle, 3);
+        let monitor = unsafe { &*((*handle).inner as *const RtlolaMonitor) };
+        assert_eq!(monitor.recv_retries, 3);
+        rtlola_monitor_free(handle);
+    }
+
+
+    #[test]
+    fn current_output_accessors_by_type() {
+        let handle = ball_handle();
+        let mut float = 0.0;
+        assert!(!rtlola_current_output_float(handle, c"hotInFar".as_ptr(), &mut float));
+        let inputs = ball_inputs(1.0, 0.0, 40.0);
+        rtlola_free_string(rtlola_process_inputs(handle, inputs.as_ptr(), 3, 1.0));
+
+        assert!(rtlola_current_output_float(handle, c"hotInFar".as_ptr(), &mut float));
+        assert_eq!(float, 104.0);
+        let mut hot = false;
+        assert!(rtlola_current_output_bool(handle, c"hot".as_ptr(), &mut hot));
+        assert!(hot);
+        // The wrong type leaves out unchanged
+        let mut uint = 7;
+        assert!(!rtlola_current_output_uint(handle, c"hot".as_ptr(), &mut uint));
+        let mut int = 7;
+        assert!(!rtlola_current_output_int(handle, c"hotInFar".as_ptr(), &mut int));
+        assert_eq!((uint, int), (7, 7));
+        assert!(rtlola_current_output_str(handle, c"hot".as_ptr()).is_null());
+        assert!(!rtlola_current_output_float(handle, c"missing".as_ptr(), &mut float));
+        assert!(!rtlola_current_output_float(handle, std::ptr::null(), &mut float));
+        rtlola_monitor_free(handle);
+    }
+
+
+    #[test]
+    fn current_output_str_returns_an_owned_copy() {
+        let names = [c"status".as_ptr(), c"code".as_ptr()];
+        let handle = rtlola_monitor_new(c"src/status_spec.lola".as_ptr(), 500, names.as_ptr(), 2);
+        assert!(rtlola_monitor_start(handle));
+        let inputs = [
+            RTLolaInput { name: std::ptr::null(), type_: RTLolaValueType::String as u32, value: RTLolaValueData { string_val: c"busy".as_ptr() } },
+            RTLolaInput { name: std::ptr::null(), type_: RTLolaValueType::UInt64 as u32, value: RTLolaValueData { uint64_val: 200 } },
+        ];
+        rtlola_free_string(rtlola_process_inputs(handle, inputs.as_ptr(), 2, 1.0));
+        let status = rtlola_current_output_str(handle, c"last_status".as_ptr());
+        assert_eq!(unsafe { CStr::from_ptr(status) }, c"busy");
+        rtlola_free_string(status);
+        rtlola_monitor_free(handle);
+    }
+
+
+    #[test]
+    fn trigger_coverage_counts_through_the_handle() {
+        assert_eq!(rtlola_fired_trigger_count(std::ptr::null()), 0);
+        let handle = ball_handle();
+        assert_eq!((rtlola_fired_trigger_count(handle), rtlola_dormant_trigger_count(handle)), (0, 5));
+        let inputs = ball_inputs(1.0, -1.0, 20.0);
+        rtlola_free_string(rtlola_process_inputs(handle, inputs.as_ptr(), 3, 1.0));
+        assert_eq!((rtlola_fired_trigger_count(handle), rtlola_dormant_trigger_count(handle)), (1, 4));
+        rtlola_monitor_free(handle);
+    }
+
+
+    #[test]
+    fn edge_mode_codes_through_the_handle() {
+        let handle = ball_handle();
+        assert!(rtlola_set_output_edge_mode(handle, c"hot".as_ptr(), 3));
+        assert!(!rtlola_set_output_edge_mode(handle, c"hot".as_ptr(), 4));
+        assert!(!rtlola_set_output_edge_mode(handle, c"cold".as_ptr(), 1));
+        assert!(!rtlola_set_output_edge_mode(handle, std::ptr::null(), 1));
+        // hot is first reported when it turns true
+        for (temperature, time, reported) in [(20.0, 1.0, false), (20.0, 2.0, false), (40.0, 3.0, true)] {
+            let inputs = ball_inputs(1.0, 0.0, temperature);
+            let verdict = rtlola_process_inputs(handle, inputs.as_ptr(), 3, time);
+            let text = unsafe { CStr::from_ptr(verdict) }.to_str().unwrap().to_string();
+            rtlola_free_string(verdict);
+            assert_eq!(text.contains("[hot]"), reported, "{}", text);
+        }
+        rtlola_monitor_free(handle);
+    }
+}
@@ -1,19 +1,13 @@
-mod rtlola_monitor;
-use std::fs;
+use rtlola_integration::error::RtlolaError;
+use rtlola_integration::rtlola_monitor::{make_float, RtlolaMonitor};
 
-use rtlola_interpreter::{monitor::Incremental, queued::VerdictKind, Value};
-use rtlola_monitor::RtlolaMonitor;
-use ordered_float::{Float, NotNan};
 
-use std::os::raw::{c_char,c_int, c_double, c_uint ,c_ulong, c_long };
-
-
-fn main() -> Result<(), String> {
+fn main() -> Result<(), RtlolaError> {
     // Example specification monitoring ball height
     let spec_file = "src/ball_spec.lola";
   
     // Create monitor with dynamic inputs
-    let mut monitor = RtlolaMonitor::new(&spec_file, 500, &["height", "velocity", "temperature"])?;
+    let mut monitor = RtlolaMonitor::new(spec_file, 500, &["height", "velocity", "temperature"])?;
     monitor.start()?;
 
     // Test data: (height, velocity, temperature, description)
@@ -41,9 +35,9 @@ fn main() -> Result<(), String> {
                 height, velocity, temp);
 
         let inputs = vec![
-            Value::Float(NotNan::try_from(*height).unwrap()),
-            Value::Float(NotNan::new(*velocity).unwrap()),
-            Value::Float(NotNan::new(*temp).unwrap()),
+            make_float(*height)?,
+            make_float(*velocity)?,
+            make_float(*temp)?,
         ];
         //let elapsed_test = 0.93222 as f64;  // Gives 922200
         match monitor.process_event_verdict(inputs, None) {
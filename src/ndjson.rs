@@ -0,0 +1,248 @@
+use std::io::{BufRead, Lines};
+use std::time::Duration;
+use ordered_float::NotNan;
+use rtlola_interpreter::{rtlola_mir::{IntTy, Type, UIntTy}, Value};
+use crate::error::RtlolaError;
+use crate::rtlola_monitor::{event_time, RtlolaMonitor};
+use crate::source::EventSource;
+use crate::verdict::VerdictReport;
+
+// Newline-delimited JSON events as an EventSource, in the format of RtlolaMonitor::run_ndjson_reader.
+// Unlike run_ndjson_reader every line must contain `time_field`, and inputs missing from a line receive Value::None.
+pub struct NdjsonSource<R> {
+    lines: std::iter::Enumerate<Lines<R>>,
+    time_field: String,
+    inputs: Vec<(String, Type)>, // Input names of the monitor in event order and the type each one feeds
+}
+
+impl<R: BufRead> NdjsonSource<R> {
+    // Read events for the inputs of `monitor`. Names the spec does not declare are rejected like unknown names.
+    pub fn new(reader: R, time_field: &str, monitor: &RtlolaMonitor) -> Self {
+        let inputs = monitor.input_names().iter()
+            .filter_map(|name| monitor.input_type(name).map(|ty| (name.clone(), ty.clone())))
+            .collect();
+        Self { lines: reader.lines().enumerate(), time_field: time_field.to_string(), inputs }
+    }
+
+    fn parse(&self, line: &str, line_no: usize) -> Result<(Vec<Value>, f64), RtlolaError> {
+        let input_type = |name: &str| self.inputs.iter().find(|(n, _)| n == name).map(|(_, ty)| ty);
+        let (named, time) = parse_line(line, line_no, &self.time_field, input_type)?;
+        let time = time.ok_or_else(|| RtlolaError::Record { line: line_no, reason: format!("{} is missing", self.time_field) })?;
+        let mut values = vec![Value::None; self.inputs.len()];
+        for (name, value) in named {
+            if let Some(pos) = self.inputs.iter().position(|(n, _)| *n == name) {
+                values[pos] = value;
+            }
+        }
+        Ok((values, time.as_secs_f64()))
+    }
+}
+
+impl<R: BufRead> EventSource for NdjsonSource<R> {
+    fn next_event(&mut self) -> Option<Result<(Vec<Value>, f64), RtlolaError>> {
+        loop {
+            let (i, line) = self.lines.next()?;
+            let line_no = i + 1;
+            match line {
+                Ok(line) if line.trim().is_empty() => continue,
+                Ok(line) => return Some(self.parse(&line, line_no)),
+                Err(e) => return Some(Err(RtlolaError::Record { line: line_no, reason: e.to_string() })),
+            }
+        }
+    }
+}
+
+impl RtlolaMonitor {
+    // Process one event per line of newline-delimited JSON, e.g. from stdin or a socket.
+    // Each line is an object mapping input names to values; `time_field` holds the event time in seconds
+    // and may be omitted to use the monitor's clock. Blank lines are skipped. A line that cannot be read, parsed or
+    // processed yields RtlolaError::Record with its 1-based line number and the reader continues with the next line.
+    pub fn run_ndjson_reader<R: BufRead>(&mut self, reader: R, time_field: &str) -> impl Iterator<Item = Result<VerdictReport, RtlolaError>> {
+        reader.lines().enumerate().filter_map(move |(i, line)| {
+            let line_no = i + 1;
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => return Some(Err(RtlolaError::Record { line: line_no, reason: e.to_string() })),
+            };
+            if line.trim().is_empty() {
+                return None;
+            }
+            Some(self.process_ndjson_line(&line, line_no, time_field))
+        })
+    }
+
+    // Convert a JSON value to the type the spec declares for an input: numbers become Unsigned, Signed or Float,
+    // booleans Bool, strings Str and arrays tuples. JSON null means no new value. A value that does not fit the
+    // type, e.g. a negative or fractional number for an unsigned input or 300 for a UInt8 input, fails with
+    // RtlolaError::InputType.
+    pub fn value_from_json(&self, input_name: &str, json: &serde_json::Value) -> Result<Value, RtlolaError> {
+        let ty = self.input_type(input_name).ok_or_else(|| RtlolaError::UnknownInput(input_name.to_string()))?;
+        json_to_value(ty, json).ok_or_else(|| RtlolaError::InputType {
+            name: input_name.to_string(),
+            expected: ty.to_string(),
+            got: json.to_string(),
+        })
+    }
+
+    fn process_ndjson_line(&mut self, line: &str, line_no: usize, time_field: &str) -> Result<VerdictReport, RtlolaError> {
+        let (named, time) = parse_line(line, line_no, time_field, |name| self.input_type(name))?;
+        let named = named.iter().map(|(name, value)| (name.as_str(), value.clone())).collect::<Vec<_>>();
+        self.process_named_event(&named, time, None)
+            .map_err(|e| RtlolaError::Record { line: line_no, reason: e.to_string() })
+    }
+}
+
+// The (input name, value) pairs of a line and its time
+type ParsedLine = (Vec<(String, Value)>, Option<Duration>);
+
+// Split an NDJSON line into its (input name, value) pairs and the value of `time_field`, if present
+fn parse_line<'a>(
+    line: &str,
+    line_no: usize,
+    time_field: &str,
+    input_type: impl Fn(&str) -> Option<&'a Type>,
+) -> Result<ParsedLine, RtlolaError> {
+    let record = |reason: String| RtlolaError::Record { line: line_no, reason };
+    let json: serde_json::Value = serde_json::from_str(line).map_err(|e| record(e.to_string()))?;
+    let object = json.as_object().ok_or_else(|| record("not a JSON object".to_string()))?;
+
+    let mut time = None;
+    let mut named = Vec::with_capacity(object.len());
+    for (key, value) in object {
+        if key == time_field {
+            let secs = value.as_f64().ok_or_else(|| record(format!("{} is not a number", time_field)))?;
+            time = Some(event_time(secs).map_err(|e| record(e.to_string()))?);
+            continue;
+        }
+        let ty = input_type(key).ok_or_else(|| record(RtlolaError::UnknownInput(key.clone()).to_string()))?;
+        let value = json_to_value(ty, value)
+            .ok_or_else(|| record(format!("{} is not a valid {}", key, ty)))?;
+        named.push((key.clone(), value));
+    }
+    Ok((named, time))
+}
+
+// Coerce a JSON value to the declared type, None if it does not fit. JSON null means no new value.
+pub(crate) fn json_to_value(ty: &Type, json: &serde_json::Value) -> Option<Value> {
+    match (ty, json) {
+        (_, serde_json::Value::Null) => Some(Value::None),
+        (Type::Option(inner), _) => json_to_value(inner, json),
+        (Type::Bool, serde_json::Value::Bool(b)) => Some(Value::Bool(*b)),
+        (Type::UInt(width), serde_json::Value::Number(n)) => n.as_u64().filter(|&u| u <= uint_max(width)).map(Value::Unsigned),
+        (Type::Int(width), serde_json::Value::Number(n)) => n.as_i64().filter(|&i| int_range(width).contains(&i)).map(Value::Signed),
+        (Type::Float(_), serde_json::Value::Number(n)) => n.as_f64().and_then(|f| NotNan::new(f).ok()).map(Value::Float),
+        (Type::String, serde_json::Value::String(s)) => Some(Value::Str(s.as_str().into())),
+        (Type::Tuple(tys), serde_json::Value::Array(items)) if tys.len() == items.len() => {
+            tys.iter().zip(items).map(|(ty, item)| json_to_value(ty, item)).collect::<Option<Vec<_>>>().map(|vals| Value::Tuple(vals.into()))
+        },
+        _ => None,
+    }
+}
+
+fn uint_max(width: &UIntTy) -> u64 {
+    match width {
+        UIntTy::UInt8 => u8::MAX.into(),
+        UIntTy::UInt16 => u16::MAX.into(),
+        UIntTy::UInt32 => u32::MAX.into(),
+        UIntTy::UInt64 => u64::MAX,
+    }
+}
+
+fn int_range(width: &IntTy) -> std::ops::RangeInclusive<i64> {
+    match width {
+        IntTy::Int8 => i8::MIN.into()..=i8::MAX.into(),
+        IntTy::Int16 => i16::MIN.into()..=i16::MAX.into(),
+        IntTy::Int32 => i32::MIN.into()..=i32::MAX.into(),
+        IntTy::Int64 => i64::MIN..=i64::MAX,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use super::*;
+
+    fn monitor() -> RtlolaMonitor {
+        let mut monitor = RtlolaMonitor::builder("", &["speed", "gear"])
+            .spec_text("input speed: Float64\ninput gear: UInt8\noutput fast := speed > 100.0")
+            .build()
+            .unwrap();
+        monitor.start().unwrap();
+        monitor
+    }
+
+    fn error_lines(results: Vec<Result<VerdictReport, RtlolaError>>) -> Vec<usize> {
+        results.into_iter()
+            .filter_map(|result| match result {
+                Err(RtlolaError::Record { line, .. }) => Some(line),
+                Err(e) => panic!("error without a line number: {}", e),
+                Ok(_) => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reader_errors_carry_their_line() {
+        let mut monitor = monitor();
+        let input = [
+            r#"{"t": 1.0, "speed": 50.0, "gear": 2}"#,
+            r#"{"t": 2.0, "altitude": 3.0}"#,
+            r#"{"t": -1.0, "speed": 60.0}"#,
+            "",
+            r#"{"t": 0.5, "speed": 60.0}"#,
+            r#"{"t": 3.0, "gear": -1}"#,
+            r#"not json"#,
+        ].join("\n");
+        let results = monitor.run_ndjson_reader(Cursor::new(input), "t").collect::<Vec<_>>();
+        // Line 5 goes back in time
+        assert_eq!(error_lines(results), [2, 3, 5, 6, 7]);
+    }
+
+    #[test]
+    fn source_errors_carry_their_line() {
+        let monitor = monitor();
+        let input = [r#"{"t": 1.0, "speed": 50.0}"#, r#"{"t": 2.0, "altitude": 3.0}"#, r#"{"speed": 3.0}"#].join("\n");
+        let mut source = NdjsonSource::new(Cursor::new(input), "t", &monitor);
+        assert!(matches!(source.next_event(), Some(Ok(_))));
+        assert!(matches!(source.next_event(), Some(Err(RtlolaError::Record { line: 2, .. }))));
+        assert!(matches!(source.next_event(), Some(Err(RtlolaError::Record { line: 3, .. }))));
+        assert!(source.next_event().is_none());
+    }
+
+    fn typed_monitor() -> RtlolaMonitor {
+        let spec = "input u: UInt64\ninput i: Int64\ninput f: Float64\ninput b: Bool\ninput s: String\ninput gear: UInt8\ninput small: Int16\noutput o := f";
+        RtlolaMonitor::builder("", &["u", "i", "f", "b", "s", "gear", "small"]).spec_text(spec).build().unwrap()
+    }
+
+    #[test]
+    fn json_numbers_become_the_declared_number_type() {
+        let monitor = typed_monitor();
+        assert_eq!(monitor.value_from_json("u", &serde_json::json!(3)).unwrap(), Value::Unsigned(3));
+        assert_eq!(monitor.value_from_json("i", &serde_json::json!(-3)).unwrap(), Value::Signed(-3));
+        assert_eq!(monitor.value_from_json("gear", &serde_json::json!(255)).unwrap(), Value::Unsigned(255));
+        assert_eq!(monitor.value_from_json("small", &serde_json::json!(-32768)).unwrap(), Value::Signed(-32768));
+        assert_eq!(monitor.value_from_json("f", &serde_json::json!(1.5)).unwrap(), Value::Float(NotNan::new(1.5).unwrap()));
+        // Integers are valid floats
+        assert_eq!(monitor.value_from_json("f", &serde_json::json!(2)).unwrap(), Value::Float(NotNan::new(2.0).unwrap()));
+    }
+
+    #[test]
+    fn json_bools_and_strings_keep_their_type() {
+        let monitor = typed_monitor();
+        assert_eq!(monitor.value_from_json("b", &serde_json::json!(true)).unwrap(), Value::Bool(true));
+        assert_eq!(monitor.value_from_json("s", &serde_json::json!("ok")).unwrap(), Value::Str("ok".into()));
+        assert_eq!(monitor.value_from_json("s", &serde_json::Value::Null).unwrap(), Value::None);
+    }
+
+    #[test]
+    fn mismatched_json_values_are_rejected() {
+        let monitor = typed_monitor();
+        for (name, json) in [("u", serde_json::json!(-1)), ("u", serde_json::json!(1.5)), ("i", serde_json::json!("3")), ("b", serde_json::json!(1)), ("gear", serde_json::json!(300)), ("small", serde_json::json!(-40000))] {
+            assert!(
+                matches!(monitor.value_from_json(name, &json), Err(RtlolaError::InputType { name: ref n, .. }) if n == name),
+                "{} accepted {}", name, json
+            );
+        }
+        assert!(matches!(monitor.value_from_json("x", &serde_json::json!(1)), Err(RtlolaError::UnknownInput(_))));
+    }
+}
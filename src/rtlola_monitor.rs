@@ -1,239 +1,2912 @@
-use std::collections::HashMap;
 use std::time::{Duration, Instant};
-use std::convert::Infallible;
-use ordered_float::Float;
-use rtlola_frontend::mir::InputReference;
 use rtlola_frontend::ParserConfig;
-use rtlola_interpreter::input::VectorFactory;
 use rtlola_interpreter::{
     monitor::{Change, TotalIncremental},
-    config::OfflineMode,
-    queued::{QueuedMonitor, QueuedVerdict, VerdictKind},
+    queued::{QueuedVerdict, VerdictKind},
     time::RelativeFloat,
-    ConfigBuilder, Value ,
-    rtlola_mir::OutputKind, 
+    Value ,
+    rtlola_mir::{
+        ActivationCondition, Constant, ExpressionKind, InputReference, OutputKind, OutputStream, PacingLocality, PacingType, RtLolaMir,
+        Stream, StreamAccessKind, StreamReference, TimeDrivenStream, Type, Offset,
+    },
 };
+use std::fmt::{self, Write};
+use std::io;
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::fs;
+use std::thread;
 use crossbeam_channel::Receiver;
-use colored::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use ordered_float::NotNan;
+use crate::annotations::parse_annotations;
+use crate::backend::{Backend, Verdict};
+pub use crate::backend::{InputTime, MonitorMode};
+use crate::background::Background;
+use crate::builder::RtlolaMonitorBuilder;
+use crate::clock::Clock;
+use crate::error::RtlolaError;
+use crate::format::{deadline_text, ColorTheme, EchoInputs, FormatSettings, OutputFormat, Seconds, VerdictStyle};
+use crate::summary::{RunStats, RunSummary, WindowSummary};
+use crate::verdict::{PendingSources, VerdictReport};
+
+// Build a float Value, rejecting NaN
+pub fn make_float(x: f64) -> Result<Value, RtlolaError> {
+    NotNan::new(x)
+        .map(Value::Float)
+        .map_err(|_| RtlolaError::NanFloat)
+}
+
+// Convert an event time in seconds, rejecting negative, NaN and infinite times
+pub fn event_time(secs: f64) -> Result<Duration, RtlolaError> {
+    Duration::try_from_secs_f64(secs).map_err(|_| RtlolaError::InvalidTime(secs))
+}
+
+// How string inputs that are not valid UTF-8 are handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum InputValidation {
+    // Invalid sequences are replaced with U+FFFD
+    #[default]
+    Lossy,
+    // Invalid strings are rejected with RtlolaError::InvalidUtf8
+    StrictUtf8,
+}
+
+// Build a string Value from raw bytes. `position` is the input's position, reported on error.
+pub fn make_str(bytes: &[u8], position: usize, validation: InputValidation) -> Result<Value, RtlolaError> {
+    let s = match validation {
+        InputValidation::Lossy => String::from_utf8_lossy(bytes).into_owned(),
+        InputValidation::StrictUtf8 => std::str::from_utf8(bytes)
+            .map_err(|_| RtlolaError::InvalidUtf8(position))?
+            .to_owned(),
+    };
+    Ok(Value::Str(s.into()))
+}
+
+// When an output stream is evaluated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Pacing {
+    // Whenever the activation condition over the named streams holds, e.g. "height & velocity"
+    Event(String),
+    // With a fixed period on a global clock
+    Periodic(Duration),
+    // With a fixed period relative to the spawn of each instance
+    LocalPeriodic(Duration),
+    // The value is always present
+    Constant,
+    // The eval clauses of the stream have different pacings, one per clause
+    Mixed(Vec<Pacing>),
+}
+
+// What happens to an event whose time is before the previous event's time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum OutOfOrderPolicy {
+    // Fail with RtlolaError::TimeRegression
+    #[default]
+    Reject,
+    // Process the event at the previous event's time
+    Clamp,
+    // Hand the time to the interpreter unchanged
+    Allow,
+}
+
+// What happens to an event whose time equals the previous event's time, e.g. the same event fed twice
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DuplicateTimePolicy {
+    // Process the event
+    #[default]
+    Allow,
+    // Log a warning and process the event
+    Warn,
+    // Fail with RtlolaError::DuplicateTimestamp
+    Reject,
+}
+
+// What construction does when the monitor mode cannot handle the spec's timing constructs correctly
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ModeCheck {
+    Off,
+    // Log a warning and build the monitor anyway
+    #[default]
+    Warn,
+    // Fail with RtlolaError::ModeIncompatible
+    Error,
+}
+
+// What submitting an event does when the number of pending verdicts reached the verdict limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum OverflowPolicy {
+    // Wait for a consumer to take verdicts, failing with RtlolaError::Timeout after the monitor's timeout
+    #[default]
+    Block,
+    // Discard the oldest pending verdicts. They still count in the run statistics and dropped_verdicts.
+    DropOldest,
+}
+
+// What submitting an event does while the monitor is paused. Either way the event fails with RtlolaError::Paused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PausePolicy {
+    // Discard the event
+    #[default]
+    Reject,
+    // Keep the event with its time and process it on resume
+    Buffer,
+}
+
+// When a boolean output appears in verdict strings and reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EdgeMode {
+    // Whenever it produces a value
+    #[default]
+    Level,
+    // Only when it changes from false to true
+    RisingEdge,
+    // Only when it changes from true to false
+    FallingEdge,
+    // Only when it changes
+    BothEdges,
+}
+
+// A closure registered with RtlolaMonitor::on_verdict
+type VerdictCallback = Box<dyn FnMut(&VerdictReport) + Send>;
 
 pub struct RtlolaMonitor {
-    start_time: Instant,
-    monitor: QueuedMonitor<VectorFactory<Infallible, Vec<Value>>, OfflineMode<RelativeFloat>, TotalIncremental, RelativeFloat>,
-    timeout: Duration,
-    receiver: Receiver<QueuedVerdict<TotalIncremental, RelativeFloat>>,
-    input_names: Vec<String>, // Track input names for validation
+    clock: Option<Box<dyn Clock>>, // Times events processed without an explicit time
+    pub(crate) spec: Option<String>, // Source of the specification, None if the monitor was built from a parsed MIR
+    annotations: HashMap<String, HashMap<String, String>>, // Read from the spec source, see stream_annotations
+    monitor: Backend,
+    pub(crate) timeout: Option<Duration>, // How long to wait for a verdict, None blocks until it arrives
+    pub(crate) recv_retries: u32, // Further waits of the same timeout before a missing verdict is a Timeout
+    receiver: Receiver<Verdict>,
+    pub(crate) input_names: Vec<String>, // Track input names for validation
+    pub(crate) input_map: Option<Vec<Option<usize>>>, // Spec input of each name, when the names do not match the spec positionally
+    pub(crate) input_refs: Option<Vec<InputReference>>, // Mapping given by the caller, kept for config
+    output_buffer: String, // Reused by process_event_verdict_str
+    background: Option<Background>, // Set while verdicts are consumed by run_background
+    sources: PendingSources, // Source labels of events whose verdict was not consumed yet
+    pub(crate) format: FormatSettings, // How verdict strings are written
+    pub(crate) policy: EventPolicy, // How events are checked and converted before the interpreter receives them
+    pub(crate) selection: OutputSelection, // Which output changes are reported
+    stats: RunStats, // Aggregated by every consumer of verdicts, returned by finalize
+    edge_values: HashMap<usize, bool>, // Latest boolean value of each output with an edge mode
+    last_inputs: Vec<Value>, // Input values of the previous formatted event, for EchoInputs::Changed
+    last_time: Option<Duration>, // Time of the latest accepted event
+    sink: Option<Box<dyn io::Write + Send>>, // Destination of process_event_to_sink
+    pub(crate) csv_sink: Option<Box<dyn io::Write + Send>>, // Receives every report as CSV rows, see set_csv_sink
+    verdict_callbacks: Vec<VerdictCallback>, // Registered with on_verdict, in registration order
+    expired: bool, // An event exceeded max_duration
+    history: VecDeque<VerdictReport>, // The most recent verdicts, oldest first
+    history_len: usize, // Capacity of history, 0 keeps no history
+    output_values: HashMap<String, Value>, // Latest value of each named output received by this monitor
+    changed: Vec<(String, Value, Value)>, // Outputs whose value changed in the latest received verdict
+    last_report: Option<VerdictReport>, // Report of the latest verdict received by any process_* method but process_event_quiet
+    first_time: Option<Duration>, // Time of the first accepted event
+    seen_inputs: Vec<bool>, // Whether each input name has received a value other than Value::None
+    pub(crate) mode_check: ModeCheck, // Applied at construction, kept for config
+    pub(crate) require_triggers: bool, // Checked at construction, kept for config
+    dropped_verdicts: u64, // Discarded under OverflowPolicy::DropOldest
+    started: bool, // start succeeded since the interpreter was built
+    paused: bool,
+    paused_events: VecDeque<(Vec<Value>, Duration, Option<String>)>, // Buffered under PausePolicy::Buffer
+    flush_on_drop: bool,
+}
+
+// Checks and conversions applied to every event before the interpreter receives it
+pub(crate) struct EventPolicy {
+    pub(crate) input_validation: InputValidation, // Applied by callers building string inputs from raw bytes
+    pub(crate) out_of_order: OutOfOrderPolicy,
+    pub(crate) duplicate_time: DuplicateTimePolicy,
+    pub(crate) max_duration: Option<Duration>, // Events after this time are rejected
+    pub(crate) pause_policy: PausePolicy,
+    pub(crate) verdict_limit: Option<usize>, // Most verdicts left pending when an event is submitted, None for no limit
+    pub(crate) overflow: OverflowPolicy,
+    pub(crate) input_transforms: Vec<Option<(f64, f64)>>, // Scale and offset applied to float values of each input name
+}
+
+// Which output changes reach reports, verdict strings and callbacks
+#[derive(Default)]
+pub(crate) struct OutputSelection {
+    pub(crate) filter: Option<Vec<bool>>, // Whether each output stream is reported, None reports all
+    pub(crate) edge_modes: HashMap<usize, EdgeMode>, // By output index, outputs without an entry are reported at every level
 }
 
 impl RtlolaMonitor {
     
-    const DEFAULT_THRESHOLD: f64 = 1e-6;
+    pub(crate) const DEFAULT_THRESHOLD: f64 = 1e-6;
+    pub(crate) const DEFAULT_ZERO_EPSILON: f64 = 1e-10;
+    pub(crate) const DEFAULT_PRECISION: usize = 6;
+    // History length kept by set_record_log, enough for test runs while bounding the memory of long ones
+    pub const RECORD_LOG_LEN: usize = 1_000_000;
+
+    pub fn new(spec_path: &str, timeout_ms: u64, input_names: &[&str]) -> Result<Self, RtlolaError> {
+        Self::new_owned(spec_path, timeout_ms, input_names.iter().map(|name| name.to_string()).collect())
+    }
+
+    // Like new, for input names built at runtime that the caller already owns
+    pub fn new_owned(spec_path: &str, timeout_ms: u64, input_names: Vec<String>) -> Result<Self, RtlolaError> {
+        Self::builder(spec_path, &[])
+            .input_names_owned(input_names)
+            .timeout_ms(timeout_ms)
+            .mode(MonitorMode::Offline)
+            .build()
+    }
+
+    pub fn builder(spec_path: &str, input_names: &[&str]) -> RtlolaMonitorBuilder {
+        RtlolaMonitorBuilder::new(spec_path, input_names)
+    }
+
+    // Create a monitor in online mode, where the interpreter stamps events with the system time
+    pub fn new_online(spec_path: &str, timeout_ms: u64, input_names: &[&str]) -> Result<Self, RtlolaError> {
+        Self::with_mode(spec_path, timeout_ms, input_names, MonitorMode::Online)
+    }
+
+    // Create a monitor from an already parsed specification, e.g. one shared with other tools, without parsing it again
+    pub fn from_mir(mir: RtLolaMir, timeout_ms: u64, input_names: &[&str]) -> Result<Self, RtlolaError> {
+        Self::builder("", input_names)
+            .mir(mir)
+            .timeout_ms(timeout_ms)
+            .build()
+    }
+
+    // Create a monitor whose input names feed the given spec inputs, e.g. to follow an external schema.
+    // Each reference must be below the number of inputs the spec declares.
+    pub fn with_input_mapping(spec_path: &str, timeout_ms: u64, mapping: Vec<(String, InputReference)>) -> Result<Self, RtlolaError> {
+        Self::builder(spec_path, &[])
+            .input_mapping(mapping)
+            .timeout_ms(timeout_ms)
+            .build()
+    }
 
-    pub fn new(spec_path: &str, timeout_ms: u64, input_names: &[&str]) -> Result<Self, String> {
+    pub fn with_mode(spec_path: &str, timeout_ms: u64, input_names: &[&str], mode: MonitorMode) -> Result<Self, RtlolaError> {
+        Self::builder(spec_path, input_names)
+            .timeout_ms(timeout_ms)
+            .mode(mode)
+            .build()
+    }
 
+    pub(crate) fn from_builder(builder: RtlolaMonitorBuilder) -> Result<Self, RtlolaError> {
+        let RtlolaMonitorBuilder {
+            spec_path, spec_text, ir, input_names, input_refs, timeout, mode, input_time, clock, style, strict_input_check,
+            input_validation, out_of_order, duplicate_time, quiet_unchanged, zero_epsilon, mode_check, verdict_limit, overflow,
+            color, require_triggers,
+        } = builder;
 
-        let spec = fs::read_to_string(spec_path)
-        .map_err(|e| format!("Failed to read specification file {}: {}", spec_path, e))?;
-        // Parse spec and validate input count matches
-        let ir = ParserConfig::for_string(spec.to_string()).parse()
-            .map_err(|e| format!("Failed to parse specification: {:?}", e))?;
+        let (spec, ir) = match ir {
+            Some(ir) => (None, ir),
+            None => {
+                let spec = match spec_text {
+                    Some(spec) => spec,
+                    None => fs::read_to_string(&spec_path).map_err(|e| RtlolaError::SpecRead(spec_path.clone(), e))?,
+                };
+                // Parse spec and validate input count matches
+                let ir = rtlola_frontend::parse(&ParserConfig::for_string(spec.clone()))
+                    .map_err(|e| RtlolaError::Parse(format!("{:?}", e)))?;
+                (Some(spec), ir)
+            },
+        };
 
-        if ir.inputs.len() != input_names.len() {
-            return Err(format!(
-                "Spec requires {} inputs but {} names provided",
+        if let Some(refs) = &input_refs
+            && let Some((name, &reference)) = input_names.iter().zip(refs).find(|(_, reference)| **reference >= ir.inputs.len())
+        {
+            return Err(RtlolaError::InputReference { name: name.clone(), reference, inputs: ir.inputs.len() });
+        }
+        let names_match = input_refs.is_some() || ir.inputs.len() == input_names.len();
+        if !names_match {
+            if strict_input_check {
+                return Err(RtlolaError::InputNames {
+                    expected: ir.inputs.len(),
+                    got: input_names.len(),
+                });
+            }
+            log_error!(
+                "Warning: spec requires {} inputs but {} names provided, matching inputs by name",
                 ir.inputs.len(),
                 input_names.len()
-            ));
+            );
         }
 
-        // Create input mapping
-        
-        let map: HashMap<String, InputReference> = input_names
-            .iter()
-            .enumerate()
-            .map(|(i, name)| {
-                // Convert index to u32 (common type for InputReference)
-                let index = i.try_into().expect("Too many inputs for u32");
-                (name.to_string(),index)
-            })
-            .collect();
-
-        let monitor = ConfigBuilder::new()
-            .spec_str(&spec)
-            .offline::<RelativeFloat>()
-            .with_event_factory::<VectorFactory<Infallible, Vec<Value>>>()
-            .with_verdict::<TotalIncremental>()
-            .queued_monitor_with_data(input_names.len());
+        if let Some(reason) = Self::mode_incompatibility(&ir, mode) {
+            match mode_check {
+                ModeCheck::Off => {},
+                ModeCheck::Warn => log_error!("Warning: {}", reason),
+                ModeCheck::Error => return Err(RtlolaError::ModeIncompatible { reason }),
+            }
+        }
+
+        if require_triggers && !ir.outputs.iter().any(|output| matches!(output.kind, OutputKind::Trigger(_))) {
+            return Err(RtlolaError::NoTriggers);
+        }
+
+        let num_inputs = ir.inputs.len();
+        let monitor = Backend::new(ir, mode, input_time, num_inputs);
+        let input_map = match &input_refs {
+            Some(refs) => Some(refs.iter().copied().map(Some).collect()),
+            None => (!names_match).then(|| {
+                input_names.iter()
+                    .map(|name| monitor.ir().inputs.iter().position(|input| &input.name == name))
+                    .collect()
+            }),
+        };
         
-        let receiver = monitor.output_queue().clone();
+        let receiver = monitor.output_queue();
+        let stats = RunStats::new(monitor.ir());
 
+        let seen_inputs = vec![false; input_names.len()];
+        let input_transforms = vec![None; input_names.len()];
+        let annotations = spec.as_deref().map(parse_annotations).unwrap_or_default();
         Ok(Self {
-            start_time: Instant::now(),
+            clock,
+            spec,
+            annotations,
             monitor,
-            timeout: Duration::from_millis(timeout_ms),
+            timeout,
+            recv_retries: 0,
             receiver,
-            input_names: input_names.iter().map(|s| s.to_string()).collect(),
+            input_names,
+            input_map,
+            input_refs,
+            output_buffer: String::new(),
+            background: None,
+            sources: PendingSources::default(),
+            format: FormatSettings {
+                style,
+                theme: ColorTheme::default(),
+                color,
+                echo_inputs: EchoInputs::All,
+                echo_timed_inputs: false,
+                output_formats: HashMap::new(),
+                quiet_unchanged,
+                show_seq: false,
+                rebase_time: false,
+                show_types: false,
+                zero_epsilon,
+                precision: Self::DEFAULT_PRECISION,
+            },
+            policy: EventPolicy {
+                input_validation,
+                out_of_order,
+                duplicate_time,
+                max_duration: None,
+                pause_policy: PausePolicy::Reject,
+                verdict_limit,
+                overflow,
+                input_transforms,
+            },
+            selection: OutputSelection::default(),
+            stats,
+            edge_values: HashMap::new(),
+            last_inputs: Vec::new(),
+            last_time: None,
+            sink: None,
+            csv_sink: None,
+            verdict_callbacks: Vec::new(),
+            expired: false,
+            history: VecDeque::new(),
+            history_len: 0,
+            output_values: HashMap::new(),
+            changed: Vec::new(),
+            last_report: None,
+            first_time: None,
+            seen_inputs,
+            mode_check,
+            require_triggers,
+            dropped_verdicts: 0,
+            started: false,
+            paused: false,
+            paused_events: VecDeque::new(),
+            flush_on_drop: false,
         })
     }
 
-    pub fn start(&mut self) -> Result<(), String> {
-        self.monitor.start()
-            .map_err(|e| format!("Failed to start monitor: {:?}", e))
+    // Why the spec's timing constructs are not monitored correctly in the given mode, None if they are
+    fn mode_incompatibility(ir: &RtLolaMir, mode: MonitorMode) -> Option<String> {
+        let periodic = |output: &OutputStream| {
+            matches!(output.eval.eval_pacing, PacingType::GlobalPeriodic(_) | PacingType::LocalPeriodic(_))
+        };
+        match mode {
+            // Offline time only advances with the events, so periodic streams are evaluated late or never
+            MonitorMode::Offline if !ir.outputs.is_empty() && ir.outputs.iter().all(periodic) => Some(
+                "every output is periodic, but offline mode only evaluates periodic streams when an event advances \
+                 the time; use online mode, or provide an event or call tick at least once per period"
+                    .to_string(),
+            ),
+            MonitorMode::Offline | MonitorMode::Online => None,
+        }
     }
 
-    // Process an event with the given inputs and current time
-    pub fn process_event(&mut self, inputs: Vec<Value>, current_time: Option<std::time::Duration> ) -> Result<QueuedVerdict<TotalIncremental, RelativeFloat>, String> {
-        if inputs.len() != self.input_names.len() {
-            return Err(format!(
-                "Expected {} inputs, got {}",
-                self.input_names.len(),
-                inputs.len()
-            ));
+    // The input names given at construction, in event order
+    pub fn input_names(&self) -> &[String] {
+        &self.input_names
+    }
+
+    // The exact specification text the monitor was built from, whether it was read from a file or given as a string.
+    // None for monitors built from a parsed MIR.
+    pub fn spec_source(&self) -> Option<&str> {
+        self.spec.as_deref()
+    }
+
+    pub fn mode(&self) -> MonitorMode {
+        self.monitor.mode()
+    }
+
+    // Annotations of an input or output stream, e.g. {"unit": "meters"} for a declaration preceded by `//@unit:meters`.
+    // None if the stream has none, which is always the case for monitors built from a parsed MIR.
+    pub fn stream_annotations(&self, name: &str) -> Option<HashMap<String, String>> {
+        self.annotations.get(name).cloned()
+    }
+
+    // Layout of the strings returned by process_event_verdict
+    pub fn set_style(&mut self, style: VerdictStyle) {
+        self.format.style = style;
+    }
+
+    pub fn style(&self) -> VerdictStyle {
+        self.format.style
+    }
+
+    // Colors of the verdict strings, ColorTheme::default() unless changed
+    pub fn set_theme(&mut self, theme: ColorTheme) {
+        self.format.theme = theme;
+    }
+
+    pub fn theme(&self) -> ColorTheme {
+        self.format.theme
+    }
+
+    // Format the values of a named output in verdict strings with `f` instead of format_number
+    pub fn set_output_formatter(&mut self, name: &str, f: Box<dyn Fn(&Value) -> String + Send>) -> Result<(), RtlolaError> {
+        self.set_output_format(name, OutputFormat::Custom(f))
+    }
+
+    // Append `unit` to the formatted values of a named output in verdict strings
+    pub fn set_output_unit(&mut self, name: &str, unit: &str) -> Result<(), RtlolaError> {
+        self.set_output_format(name, OutputFormat::Unit(unit.to_string()))
+    }
+
+    fn set_output_format(&mut self, name: &str, format: OutputFormat) -> Result<(), RtlolaError> {
+        if !self.outputs().iter().any(|output| output == name) {
+            return Err(RtlolaError::UnknownOutput(name.to_string()));
         }
-        
-        let elapsed = match current_time {
-        Some(time) => time,
-        None => self.start_time.elapsed(),
-        };
-        let elapsed = self.start_time.elapsed();
+        self.format.output_formats.insert(name.to_string(), format);
+        Ok(())
+    }
 
-        let test: u64 = 20.0 as u64;
+    // Apply `value * scale + offset` to the float values of a named input before they reach the interpreter,
+    // e.g. to convert raw sensor readings. Values of other types are passed unchanged.
+    pub fn set_input_transform(&mut self, name: &str, scale: f64, offset: f64) -> Result<(), RtlolaError> {
+        let idx = self.input_names.iter().position(|input| input == name)
+            .ok_or_else(|| RtlolaError::UnknownInput(name.to_string()))?;
+        self.policy.input_transforms[idx] = Some((scale, offset));
+        Ok(())
+    }
 
-        self.monitor.accept_event(inputs, elapsed)
-            .map_err(|e| format!("Failed to accept event: {:?}", e))?;
-            
-        self.receiver.recv_timeout(self.timeout)
-            .map_err(|e| match e {
-                crossbeam_channel::RecvTimeoutError::Timeout => "Timeout while waiting for verdict".to_string(),
-                crossbeam_channel::RecvTimeoutError::Disconnected => "Monitor channel disconnected".to_string(),
+    // Pass the values of a named input unchanged again. Returns false if it had no transform.
+    pub fn clear_input_transform(&mut self, name: &str) -> bool {
+        match self.input_names.iter().position(|input| input == name) {
+            Some(idx) => self.policy.input_transforms[idx].take().is_some(),
+            None => false,
+        }
+    }
+
+    // Apply the input transforms to an event's values, in input name order
+    fn transform_inputs(&self, mut inputs: Vec<Value>) -> Result<Vec<Value>, RtlolaError> {
+        for (value, transform) in inputs.iter_mut().zip(&self.policy.input_transforms) {
+            if let (Value::Float(x), Some((scale, offset))) = (&*value, transform) {
+                *value = make_float(x.into_inner() * scale + offset)?;
+            }
+        }
+        Ok(inputs)
+    }
+
+    pub fn set_out_of_order_policy(&mut self, policy: OutOfOrderPolicy) {
+        self.policy.out_of_order = policy;
+    }
+
+    // What happens to an event at the same time as the previous event, allowed by default.
+    // An event clamped by OutOfOrderPolicy::Clamp gets the previous time and counts as a duplicate.
+    pub fn set_duplicate_time_policy(&mut self, policy: DuplicateTimePolicy) {
+        self.policy.duplicate_time = policy;
+    }
+
+    // Time of the latest event handed to the interpreter
+    pub fn last_event_time(&self) -> Option<Duration> {
+        self.last_time
+    }
+
+    // Reject every event from the first one with a time after d on, with RtlolaError::DurationExceeded
+    pub fn set_max_duration(&mut self, d: Duration) {
+        self.policy.max_duration = Some(d);
+    }
+
+    // Floats with a magnitude of at most epsilon are formatted as 0.0, 1e-10 by default.
+    // Lower it for domains where tiny values are meaningful, they are then shown in scientific notation.
+    pub fn set_zero_epsilon(&mut self, epsilon: f64) {
+        self.format.zero_epsilon = epsilon;
+    }
+
+    // Decimals of formatted floats, 6 by default, in fixed and in scientific notation. Integers and strings are not
+    // affected. The magnitude below which floats switch to scientific notation does not depend on the precision.
+    pub fn set_precision(&mut self, precision: usize) {
+        self.format.precision = precision;
+    }
+
+    pub fn precision(&self) -> usize {
+        self.format.precision
+    }
+
+    // Whether trigger idx fired in the latest verdict that evaluated it: the latest event verdict for an event-paced
+    // trigger, the latest timed verdict for a periodic one. A trigger whose value is a message or another non-boolean
+    // value is active whenever it produced a value, a boolean trigger only while its value is true.
+    // False for unknown indices and before the trigger was evaluated.
+    pub fn is_trigger_active(&self, idx: usize) -> bool {
+        self.stats.trigger_active(idx).unwrap_or(false)
+    }
+
+    // Roll the verdicts up into consecutive windows of event time [k * window, (k + 1) * window).
+    // The first call for a window length starts the accumulation and returns None. Later calls return the latest
+    // window that ended since the previous call, once a verdict's time crossed its end, and None otherwise.
+    // Call it after every event to see every window, a window that is not taken is replaced by the next one.
+    pub fn windowed_summary(&mut self, window: Duration) -> Option<WindowSummary> {
+        self.stats.take_window(window)
+    }
+
+    // Keep the reports of the last len verdicts of processed events, 0 (the default) keeps none
+    pub fn set_history_len(&mut self, len: usize) {
+        self.history_len = len;
+        while self.history.len() > len {
+            self.history.pop_front();
+        }
+    }
+
+    pub fn history_len(&self) -> usize {
+        self.history_len
+    }
+
+    // Report of the verdict received by the latest process_* call, whichever form it returned the verdict in.
+    // process_event_quiet does not build reports and leaves it unchanged. None before the first verdict.
+    pub fn last_report(&self) -> Option<&VerdictReport> {
+        self.last_report.as_ref()
+    }
+
+    // The most recent verdicts, oldest first, see set_history_len
+    pub fn history(&self) -> impl Iterator<Item = &VerdictReport> {
+        self.history.iter()
+    }
+
+    // Record the reports of processed verdicts for triggers_in_range and output_history, meant for test suites
+    // that assert on a run. Sets the history length to RECORD_LOG_LEN, or to 0 when disabled.
+    pub fn set_record_log(&mut self, record: bool) {
+        self.set_history_len(if record { Self::RECORD_LOG_LEN } else { 0 });
+    }
+
+    // Times in seconds at which trigger idx fired within [from, to], from the recorded history
+    pub fn triggers_in_range(&self, idx: usize, from: f64, to: f64) -> Vec<f64> {
+        self.history.iter()
+            .map(|report| (report.time.as_secs_f64(), report))
+            .filter(|(time, report)| (from..=to).contains(time) && report.fired_triggers().any(|(fired, _)| fired == idx))
+            .map(|(time, _)| time)
+            .collect()
+    }
+
+    // Every value a named output produced as (time in seconds, value), from the recorded history.
+    // A parameterized output has one entry per instance that produced a value.
+    pub fn output_history(&self, name: &str) -> Vec<(f64, Value)> {
+        self.history.iter()
+            .flat_map(|report| report.outputs.iter().filter(|output| output.name == name).map(move |output| (report, output)))
+            .filter_map(|(report, output)| match &output.change {
+                Change::Value(_, value) => Some((report.time.as_secs_f64(), value.clone())),
+                _ => None,
             })
+            .collect()
     }
 
-    // Process an event and return a formatted string with the verdict
-    pub fn process_event_verdict(&mut self, inputs: Vec<Value>, current_time: Option<f64> ) -> Result<String, String> {
-        let elapsed = match current_time {
-            Some(seconds) => std::time::Duration::from_secs_f64(seconds),
-            None => self.start_time.elapsed()
+    // Input names that have not received a value in any accepted event yet, in input name order.
+    // An input that stays in this list after some events usually points to a name the caller never populates.
+    pub fn never_seen_inputs(&self) -> Vec<String> {
+        self.input_names.iter()
+            .zip(&self.seen_inputs)
+            .filter(|(_, seen)| !**seen)
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    // Sequence number of the latest accepted event, counting from 1, 0 before the first event
+    pub fn last_event_seq(&self) -> u64 {
+        self.stats.events()
+    }
+
+    // Prefix the verdict strings of events with their sequence number, e.g. "[#42]", off by default
+    pub fn set_show_seq(&mut self, show: bool) {
+        self.format.show_seq = show;
+    }
+
+    // Show times in verdict strings relative to the first event, so it is shown at 0.000000s, instead of relative
+    // to the start of the monitor's time, off by default. Reports and statistics keep the monitor's time.
+    pub fn set_rebase_to_first_event(&mut self, rebase: bool) {
+        self.format.rebase_time = rebase;
+    }
+
+    // Annotate input and output values in verdict strings with the stream type declared in the spec,
+    // e.g. "= 3:UInt64" or "= 1.500000:Float64", off by default
+    pub fn set_show_types(&mut self, show: bool) {
+        self.format.show_types = show;
+    }
+
+    // A verdict time in seconds as shown in verdict strings
+    fn display_time(&self, time: Duration) -> Seconds {
+        let time = match self.first_time.filter(|_| self.format.rebase_time) {
+            Some(origin) => time.saturating_sub(origin),
+            None => time,
         };
-        let verdict = self.process_event(inputs,Some(elapsed))?;
-        let ir = self.monitor.ir();
-        let ts = elapsed.as_secs_f64();
-        
-       
-        // Main output string with color codes
-        let mut string_output = String::new();
-        
-        match verdict.kind {
-            VerdictKind::Timed => {
-                string_output.push_str(&format!(
-                    "{} {}\n",
-                    format!("[{:.6}s]", ts),
-                    "[Trigger] Deadline reached".red().to_string()
-                ));
-            },
-            VerdictKind::Event => {
-                string_output.push_str(&format!(
-                    "{} {}\n",
-                    format!("[{:.6}s]", ts),
-                    "Processing new event"
-                ));
-                
-                for (idx, val) in verdict.verdict.inputs {
-                    let input = &ir.inputs[idx];
-                    string_output.push_str(&format!(
-                        "{} {} {} {}\n",
-                        format!("[{:.6}s]", ts),
-                        "[Input]".cyan().to_string(),
-                        format!("[{}]", input.name).cyan().to_string(),
-                        format!("= {}", self.format_number(val, Self::DEFAULT_THRESHOLD))
-                    ));
-                }
-            },
+        self.seconds(time)
+    }
+
+    // A time in seconds with nanosecond digits under InputTime::RelativeNanos, otherwise microsecond digits
+    pub(crate) fn seconds(&self, time: Duration) -> Seconds {
+        Seconds { time, nanos: self.input_time() == InputTime::RelativeNanos }
+    }
+
+    // Whether an event exceeded the maximum duration, after which the monitor accepts no more events
+    pub fn is_expired(&self) -> bool {
+        self.expired
+    }
+
+    // If set, verdict strings are empty for verdicts in which no output or trigger changed, off by default
+    pub fn set_quiet_unchanged(&mut self, quiet: bool) {
+        self.format.quiet_unchanged = quiet;
+    }
+
+    // Which inputs verdict strings echo, all of them by default
+    pub fn set_echo_inputs(&mut self, echo: EchoInputs) {
+        self.format.echo_inputs = echo;
+    }
+
+    // If set, timed verdicts list the latest value of every input under the echo setting, off by default.
+    // A timed verdict carries no new inputs, so EchoInputs::Changed lists none of them.
+    pub fn set_echo_timed_inputs(&mut self, echo: bool) {
+        self.format.echo_timed_inputs = echo;
+    }
+
+    pub fn echo_timed_inputs(&self) -> bool {
+        self.format.echo_timed_inputs
+    }
+
+    // Only report the listed outputs and triggers in verdict strings and reports; triggers are named trigger_<index>.
+    // Inputs are always reported. An empty list reports all streams again.
+    // The filter does not apply to run_background, whose handler sees every change.
+    pub fn set_output_filter(&mut self, names: &[&str]) -> Result<(), RtlolaError> {
+        if names.is_empty() {
+            self.selection.filter = None;
+            return Ok(());
         }
-    
-        for (out_idx, changes) in verdict.verdict.outputs {
-            let output = &ir.outputs[out_idx];
-            let (prefix, name) = match &output.kind {
-                OutputKind::NamedOutput(name) => {
-                    ("Output", format!("[Output][{}]", name).blue().to_string())
-                },
-                OutputKind::Trigger(trigger_idx) => {
-                    ("Trigger", format!("[#{}]", trigger_idx).red().to_string())
-                },
+        let outputs = &self.monitor.ir().outputs;
+        let mut filter = vec![false; outputs.len()];
+        for name in names {
+            let idx = outputs.iter()
+                .position(|output| output.name == *name)
+                .ok_or_else(|| RtlolaError::UnknownOutput(name.to_string()))?;
+            filter[idx] = true;
+        }
+        self.selection.filter = Some(filter);
+        Ok(())
+    }
+
+    // Report a boolean output only on the transitions the mode selects, e.g. only when is_safe turns false with
+    // EdgeMode::FallingEdge. Its first value is no transition and is only reported under EdgeMode::Level. Instances
+    // of a parameterized output share one previous value, spawns, closes and non-boolean values are always reported.
+    // Like the output filter, this shapes verdict strings and reports, while statistics, current_output and
+    // changed_outputs see every value.
+    pub fn set_output_edge_mode(&mut self, name: &str, mode: EdgeMode) -> Result<(), RtlolaError> {
+        let idx = self.monitor.ir().outputs.iter()
+            .position(|output| output.name == name && matches!(output.kind, OutputKind::NamedOutput(_)))
+            .ok_or_else(|| RtlolaError::UnknownOutput(name.to_string()))?;
+        match mode {
+            EdgeMode::Level => self.selection.edge_modes.remove(&idx),
+            mode => self.selection.edge_modes.insert(idx, mode),
+        };
+        Ok(())
+    }
+
+    // Drop the boolean values of outputs with an edge mode that are not a selected transition
+    fn apply_edge_modes(&mut self, verdict: &mut Verdict) {
+        for (idx, changes) in &mut verdict.verdict.outputs {
+            let Some(&mode) = self.selection.edge_modes.get(idx) else {
+                continue;
             };
-    
-            for change in changes {
-                match change {
-                    Change::Spawn(param) => {
-                        string_output.push_str(&format!(
-                            "{} {} {} {:?}\n",
-                            format!("[{:.6}s]", ts),
-                            name,
-                            "[Spawn]".purple().to_string(),
-                            param
-                        ));
-                    },
-                    Change::Value(param, val) => {
-                        let is_output = matches!(output.kind, OutputKind::NamedOutput(_));
-                        let is_trigger = matches!(output.kind, OutputKind::Trigger(_));
-                       
-                        if is_output {
-                            string_output.push_str(&format!(
-                                "{} {} {} {}\n",
-                                format!("[{:.6}s]", ts),
-                                name,
-                                "[Value] = ".green().to_string(),
-                                self.format_number(val.clone(), Self::DEFAULT_THRESHOLD)
-                            ));
-                        }   
-                        
-                        if is_trigger {
-                            string_output.push_str(&format!(
-                                "{} {} {} {}\n",
-                                format!("[{:.6}s]", ts),
-                                "[Trigger]".red().to_string(),
-                                name,
-                                format!("= {}", val)
-                            ));
-                        }
-                    },
-                    Change::Close(param) => {
-                        string_output.push_str(&format!(
-                            "{} {} {} {:?}\n",
-                            format!("[{:.6}s]", ts),
-                            name,
-                            "[Close]".yellow().to_string(),
-                            param
-                        ));
-                    },
+            changes.retain(|change| {
+                let Change::Value(_, Value::Bool(current)) = change else {
+                    return true;
+                };
+                let previous = self.edge_values.insert(*idx, *current);
+                match (mode, previous, current) {
+                    (EdgeMode::Level, _, _) => true,
+                    (_, None, _) => false,
+                    (EdgeMode::RisingEdge, Some(previous), current) => !previous && *current,
+                    (EdgeMode::FallingEdge, Some(previous), current) => previous && !*current,
+                    (EdgeMode::BothEdges, Some(previous), current) => previous != *current,
                 }
+            });
+        }
+        verdict.verdict.outputs.retain(|(idx, changes)| !changes.is_empty() || !self.selection.edge_modes.contains_key(idx));
+    }
+
+    // Names of the outputs and triggers matching a glob pattern, in spec order. `*` matches any run of characters,
+    // `?` a single character, e.g. "car_*_speed". Triggers are named trigger_<index> as in the output filter,
+    // so the result can be passed to set_output_filter.
+    pub fn outputs_matching(&self, pattern: &str) -> Vec<String> {
+        let pattern = pattern.chars().collect::<Vec<_>>();
+        self.monitor.ir().outputs.iter()
+            .filter(|output| glob_match(&pattern, &output.name.chars().collect::<Vec<_>>()))
+            .map(|output| output.name.clone())
+            .collect()
+    }
+
+    // How the C API converts string inputs, see make_str
+    pub fn input_validation(&self) -> InputValidation {
+        self.policy.input_validation
+    }
+
+    pub fn set_input_validation(&mut self, validation: InputValidation) {
+        self.policy.input_validation = validation;
+    }
+
+    pub fn input_time(&self) -> InputTime {
+        self.monitor.input_time()
+    }
+
+    // The compiled specification
+    pub fn ir(&self) -> &RtLolaMir {
+        self.monitor.ir()
+    }
+
+    // Start the interpreter. A monitor whose start failed stays not started: it can be dropped safely,
+    // and restart gives it a fresh interpreter to start again. Starting a started monitor fails without
+    // affecting it.
+    pub fn start(&mut self) -> Result<(), RtlolaError> {
+        if self.started {
+            return Err(RtlolaError::Start("monitor already started".to_string()));
+        }
+        self.monitor.start()
+            .map_err(|e| RtlolaError::Start(format!("{:?}", e)))?;
+        self.started = true;
+        Ok(())
+    }
+
+    pub fn is_started(&self) -> bool {
+        self.started
+    }
+
+    // Stop accepting events without losing any monitor state. Until resume, every process_* and submit_event call
+    // fails with RtlolaError::Paused and the event is discarded or buffered according to the pause policy.
+    // Events with given times keep them, so the first event after a rejecting pause jumps over the gap: periodic
+    // streams evaluate every deadline of the gap at once and sliding windows see no values in it. Buffered events
+    // keep their times and are processed as if there had been no pause.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    // Accept events again and process the events buffered while paused, returning their reports. While
+    // run_background runs, the events are submitted instead, their verdicts go to its handler and no reports are
+    // returned. Stops at the first failing event, which stays buffered with the events after it for the next call.
+    pub fn resume(&mut self) -> Result<Vec<VerdictReport>, RtlolaError> {
+        self.paused = false;
+        let mut reports = Vec::with_capacity(self.paused_events.len());
+        while let Some((inputs, time, source)) = self.paused_events.front().cloned() {
+            if self.background.is_some() {
+                self.submit(inputs, Some(time), source.as_deref())?;
+            } else {
+                reports.push(self.process_report(inputs, Some(time), source.as_deref())?);
             }
+            self.paused_events.pop_front();
         }
-    
-        Ok(string_output)  // Explicitly return our built string
+        Ok(reports)
     }
 
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
 
-    pub fn format_number(&self, val: Value, threshold: f64) -> String {
-        match val {
-            Value::Float(f) => {
-                if f.abs() < threshold.abs() && f.abs() > 1e-10 {  // Handle negative thresholds
-                    if f == 0.0 {
-                        "0".to_string()
-                    } else {
-                        format!("{:.6e}", f.into_inner())
-                    }
-                }
-                else if f.abs() < 1e-10 {
-                    format!("{:.1}", 0.0)
-                }    
-                 else {
-                    format!("{:.6}", f)
-                }
-            },
-            // Other variants remain the same
-            _ => val.to_string(),
+    // What events submitted while paused do, PausePolicy::Reject by default
+    pub fn set_pause_policy(&mut self, policy: PausePolicy) {
+        self.policy.pause_policy = policy;
+    }
+
+    pub fn pause_policy(&self) -> PausePolicy {
+        self.policy.pause_policy
+    }
+
+    // Events buffered while paused that resume has not processed yet
+    pub fn paused_events(&self) -> usize {
+        self.paused_events.len()
+    }
+
+    // Replace the interpreter with a fresh one for the same spec and start it, e.g. after start failed because
+    // the interpreter's worker thread ended. The run starts over: stream values, statistics, history and the time
+    // of the latest event are discarded, settings such as filters, sinks and callbacks are kept.
+    pub fn restart(&mut self) -> Result<(), RtlolaError> {
+        if self.background.is_some() {
+            return Err(RtlolaError::BackgroundRunning);
+        }
+        let ir = self.monitor.ir().clone();
+        let num_inputs = ir.inputs.len();
+        // Dropping the old interpreter closes its input queue, which ends its worker thread
+        self.monitor = Backend::new(ir, self.monitor.mode(), self.monitor.input_time(), num_inputs);
+        self.receiver = self.monitor.output_queue();
+        self.sources = PendingSources::default();
+        self.stats = RunStats::new(self.monitor.ir());
+        self.last_inputs.clear();
+        self.last_time = None;
+        self.first_time = None;
+        self.expired = false;
+        self.history.clear();
+        self.output_values.clear();
+        self.changed.clear();
+        self.last_report = None;
+        self.seen_inputs.fill(false);
+        self.dropped_verdicts = 0;
+        self.started = false;
+        self.paused = false;
+        self.paused_events.clear();
+        self.edge_values.clear();
+        self.start()
+    }
+
+    // Check that the spec runs on a working interpreter: a fresh interpreter with this monitor's configuration gets
+    // a single event at time 0 with every input at its type's default value and must return a verdict within the
+    // timeout. The fresh interpreter is discarded afterwards, so the monitor's own state is unaffected.
+    pub fn self_test(&self) -> Result<(), RtlolaError> {
+        let ir = self.monitor.ir();
+        let inputs = ir.inputs.iter().map(|input| Self::default_value(&input.ty)).collect::<Vec<_>>();
+        let mut probe = Backend::new(self.ir().clone(), self.monitor.mode(), self.monitor.input_time(), inputs.len());
+        let receiver = probe.output_queue();
+        probe.start()
+            .map_err(|e| RtlolaError::Start(format!("{:?}", e)))?;
+        probe.accept_event(inputs, Duration::ZERO)
+            .map_err(|e| RtlolaError::AcceptEvent { event: 0, time: Duration::ZERO, reason: e.to_string() })?;
+        Self::recv_verdict(&receiver, self.timeout).map(|_| ())
+    }
+
+    // Wait for the next verdict of this monitor, retrying recv_retries times before giving up with Timeout.
+    // The own verdict of a tick is skipped.
+    fn recv_next(&self) -> Result<Verdict, RtlolaError> {
+        let mut retries = 0;
+        loop {
+            match Self::recv_verdict(&self.receiver, self.timeout) {
+                Err(RtlolaError::Timeout) if retries < self.recv_retries => {
+                    retries += 1;
+                    log_error!("Warning: no verdict within the timeout, retry {} of {}", retries, self.recv_retries);
+                },
+                Ok(verdict) if self.sources.is_tick_verdict(&verdict) => {},
+                result => return result,
+            }
+        }
+    }
+
+    // How often waiting for a verdict is repeated with the same timeout before failing with Timeout, 0 by default.
+    // Smooths over scheduling hiccups under bursty load while a stalled interpreter still fails eventually.
+    pub fn set_recv_retries(&mut self, retries: u32) {
+        self.recv_retries = retries;
+    }
+
+    fn recv_verdict(receiver: &Receiver<Verdict>, timeout: Option<Duration>) -> Result<Verdict, RtlolaError> {
+        match timeout {
+            Some(timeout) => receiver.recv_timeout(timeout).map_err(|e| match e {
+                crossbeam_channel::RecvTimeoutError::Timeout => RtlolaError::Timeout,
+                crossbeam_channel::RecvTimeoutError::Disconnected => RtlolaError::Disconnected,
+            }),
+            None => receiver.recv().map_err(|_| RtlolaError::Disconnected),
+        }
+    }
+
+    fn default_value(ty: &Type) -> Value {
+        match ty {
+            Type::Bool => Value::Bool(false),
+            Type::Int(_) => Value::Signed(0),
+            Type::UInt(_) => Value::Unsigned(0),
+            Type::Float(_) => Value::Float(NotNan::default()),
+            Type::String => Value::Str("".into()),
+            Type::Bytes => Value::Bytes(Vec::new().into()),
+            Type::Tuple(tys) => Value::Tuple(tys.iter().map(Self::default_value).collect()),
+            Type::Option(inner) => Self::default_value(inner),
+            Type::Function { .. } => Value::None,
+        }
+    }
+
+    // Process an event with the given inputs and current time
+    pub fn process_event(&mut self, inputs: Vec<Value>, current_time: Option<Duration>) -> Result<VerdictReport, RtlolaError> {
+        self.process_report(inputs, current_time, None)
+    }
+
+    // Process an event and discard its verdict without building a string, or a report unless the history,
+    // the CSV sink or an on_verdict closure needs one.
+    // The fastest way to advance the monitor when only run statistics or callbacks are of interest.
+    pub fn process_event_quiet(&mut self, inputs: Vec<Value>, current_time: Option<Duration>) -> Result<(), RtlolaError> {
+        let verdict = self.process_queued(inputs, current_time, None, false)?;
+        self.sources.take(verdict.kind);
+        Ok(())
+    }
+
+    // Process an event given as (input name, value) pairs. Inputs that are not listed receive no new value.
+    // `source` labels where the event came from; it is carried into the report for diagnostics only.
+    pub fn process_named_event(&mut self, named: &[(&str, Value)], current_time: Option<Duration>, source: Option<&str>) -> Result<VerdictReport, RtlolaError> {
+        let inputs = self.named_inputs(named)?;
+        self.process_report(inputs, current_time, source)
+    }
+
+    // Process an event and return the interpreter's verdict unchanged, for use with other rtlola_interpreter utilities.
+    // Like the other process_* methods it takes the next verdict from the monitor's queue, which may be a timed
+    // verdict that precedes the event's own verdict. The output filter and run statistics still apply.
+    pub fn process_event_raw(&mut self, inputs: Vec<Value>, current_time: Option<Duration>) -> Result<QueuedVerdict<TotalIncremental, RelativeFloat>, RtlolaError> {
+        let verdict = self.process_queued(inputs, current_time, None, true)?;
+        self.sources.take(verdict.kind);
+        Ok(verdict)
+    }
+
+    // Process an event and return only the fired triggers as (trigger index, message) pairs, taken from the verdict.
+    // The report is still kept as last_report.
+    pub fn process_event_triggers(&mut self, inputs: Vec<Value>, current_time: Option<Duration>) -> Result<Vec<(usize, Value)>, RtlolaError> {
+        let verdict = self.process_queued(inputs, current_time, None, true)?;
+        self.sources.take(verdict.kind);
+        let outputs = &self.monitor.ir().outputs;
+        let mut fired = Vec::new();
+        for (out_idx, changes) in verdict.verdict.outputs {
+            if let OutputKind::Trigger(idx) = outputs[out_idx].kind {
+                fired.extend(changes.into_iter().filter_map(|change| match change {
+                    Change::Value(_, val) => Some((idx, val)),
+                    _ => None,
+                }));
+            }
+        }
+        Ok(fired)
+    }
+
+    // Process an event and return whether any trigger fired, for callers that only branch on pass or fail
+    pub fn process_event_violated(&mut self, inputs: Vec<Value>, current_time: Option<Duration>) -> Result<bool, RtlolaError> {
+        self.process_event_triggers(inputs, current_time).map(|fired| !fired.is_empty())
+    }
+
+    fn process_report(&mut self, inputs: Vec<Value>, current_time: Option<Duration>, source: Option<&str>) -> Result<VerdictReport, RtlolaError> {
+        let verdict = self.process_queued(inputs, current_time, source, true)?;
+        self.sources.take(verdict.kind);
+        Ok(self.last_report.clone().expect("process_queued keeps the report"))
+    }
+
+    // Order named values by input position, filling missing inputs with Value::None
+    fn named_inputs(&self, named: &[(&str, Value)]) -> Result<Vec<Value>, RtlolaError> {
+        let mut inputs = vec![Value::None; self.input_names.len()];
+        for (name, value) in named {
+            let idx = self.input_names.iter()
+                .position(|n| n == name)
+                .ok_or_else(|| RtlolaError::UnknownInput(name.to_string()))?;
+            inputs[idx] = value.clone();
         }
+        Ok(inputs)
     }
 
-}
\ No newline at end of file
+    // Validate the number and types of an event's inputs without handing it to the monitor.
+    // Value::None is accepted for every input since it means the input has no new value.
+    pub fn check_event(&self, values: &[Value]) -> Result<(), RtlolaError> {
+        if values.len() != self.input_names.len() {
+            return Err(RtlolaError::InputCount {
+                expected: self.input_names.len(),
+                got: values.len(),
+            });
+        }
+        let values = self.route_inputs(values.to_vec());
+        for (input, value) in self.monitor.ir().inputs.iter().zip(&values) {
+            if !Self::value_fits(&input.ty, value) {
+                return Err(RtlolaError::InputType {
+                    name: input.name.clone(),
+                    expected: input.ty.to_string(),
+                    got: Self::value_kind(value).to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    // Like process_event, but rejects the event before it reaches the monitor if check_event fails.
+    // Use it for Value vectors that did not come from the typed FFI conversions, e.g. from a custom decoder.
+    pub fn process_values(&mut self, values: Vec<Value>, current_time: Option<Duration>) -> Result<VerdictReport, RtlolaError> {
+        self.check_event(&values)?;
+        self.process_event(values, current_time)
+    }
+
+    // Declared type of the spec input the given input name feeds
+    pub fn input_type(&self, name: &str) -> Option<&Type> {
+        let pos = self.input_names.iter().position(|n| n == name)?;
+        let idx = match &self.input_map {
+            Some(map) => map[pos]?,
+            None => pos,
+        };
+        Some(&self.monitor.ir().inputs[idx].ty)
+    }
+
+    // Reorder the values of the named inputs into the spec's input order.
+    // Without an input map the names match the spec positionally.
+    fn route_inputs(&self, inputs: Vec<Value>) -> Vec<Value> {
+        let Some(map) = &self.input_map else {
+            return inputs;
+        };
+        let mut routed = vec![Value::None; self.monitor.ir().inputs.len()];
+        for (value, target) in inputs.into_iter().zip(map) {
+            if let Some(idx) = target {
+                routed[*idx] = value;
+            }
+        }
+        routed
+    }
+
+    fn value_fits(ty: &Type, value: &Value) -> bool {
+        match (ty, value) {
+            (_, Value::None) => true,
+            (Type::Option(inner), _) => Self::value_fits(inner, value),
+            (Type::Bool, Value::Bool(_))
+            | (Type::Int(_), Value::Signed(_))
+            | (Type::UInt(_), Value::Unsigned(_))
+            | (Type::Float(_), Value::Float(_))
+            | (Type::String, Value::Str(_))
+            | (Type::Bytes, Value::Bytes(_)) => true,
+            (Type::Tuple(tys), Value::Tuple(vals)) => {
+                tys.len() == vals.len() && tys.iter().zip(vals.iter()).all(|(t, v)| Self::value_fits(t, v))
+            },
+            _ => false,
+        }
+    }
+
+    fn value_kind(value: &Value) -> &'static str {
+        match value {
+            Value::None => "no value",
+            Value::Bool(_) => "Bool",
+            Value::Unsigned(_) => "unsigned integer",
+            Value::Signed(_) => "signed integer",
+            Value::Float(_) => "Float",
+            Value::Tuple(_) => "Tuple",
+            Value::Str(_) => "String",
+            Value::Bytes(_) => "Bytes",
+        }
+    }
+
+    // Process every event of a trace, stopping at the first error
+    pub fn run_trace<I>(&mut self, events: I) -> Result<Vec<VerdictReport>, RtlolaError>
+    where
+        I: IntoIterator<Item = (Vec<Value>, f64)>,
+    {
+        events
+            .into_iter()
+            .map(|(inputs, time)| self.process_event(inputs, Some(event_time(time)?)))
+            .collect()
+    }
+
+    // Process every event of a trace and hand each report to f, stopping at the first error.
+    // Unlike run_trace nothing is collected: every report replaces the previous one in the monitor's report slot,
+    // so memory stays flat however long the trace is.
+    pub fn for_each_verdict<I>(&mut self, events: I, mut f: impl FnMut(&VerdictReport)) -> Result<(), RtlolaError>
+    where
+        I: IntoIterator<Item = (Vec<Value>, f64)>,
+    {
+        for (inputs, time) in events {
+            let verdict = self.process_queued(inputs, Some(event_time(time)?), None, true)?;
+            self.sources.take(verdict.kind);
+            f(self.last_report.as_ref().expect("process_queued keeps the report"));
+        }
+        Ok(())
+    }
+
+    // Process a trace at the pace of its timestamps, sleeping for the gap between consecutive events divided by speed.
+    // A speed of 2.0 replays twice as fast, a speed that is not positive replays without sleeping.
+    // An event with an earlier time than its predecessor is not delayed and is handled by the out-of-order policy.
+    pub fn replay_realtime<I>(&mut self, events: I, speed: f64) -> Result<Vec<VerdictReport>, RtlolaError>
+    where
+        I: IntoIterator<Item = (Vec<Value>, f64)>,
+    {
+        let mut reports = Vec::new();
+        let mut prev: Option<(f64, Instant)> = None;
+        for (inputs, time) in events {
+            let current_time = event_time(time)?;
+            if let Some((prev_time, prev_instant)) = prev
+                && speed > 0.0
+                && let Ok(gap) = Duration::try_from_secs_f64((time - prev_time) / speed)
+            {
+                // Sleep until the scheduled instant so processing time does not accumulate as drift
+                if let Some(remaining) = gap.checked_sub(prev_instant.elapsed()) {
+                    std::thread::sleep(remaining);
+                }
+            }
+            prev = Some((time, Instant::now()));
+            reports.push(self.process_event(inputs, Some(current_time))?);
+        }
+        Ok(reports)
+    }
+
+    // Process every event of a trace, logging failed events and continuing with the next one
+    pub fn process_trace_resilient<I>(&mut self, events: I) -> Vec<Result<VerdictReport, RtlolaError>>
+    where
+        I: IntoIterator<Item = (Vec<Value>, f64)>,
+    {
+        events
+            .into_iter()
+            .enumerate()
+            .map(|(i, (inputs, time))| {
+                let result = event_time(time).and_then(|time| self.process_event(inputs, Some(time)));
+                if let Err(e) = &result {
+                    log_error!("Event {} at {:.6}s failed: {}", i, time, e);
+                }
+                result
+            })
+            .collect()
+    }
+
+    // Hand an event to the monitor without waiting for its verdict.
+    // Use this while run_background delivers the verdicts.
+    pub fn submit_event(&mut self, inputs: Vec<Value>, current_time: Option<Duration>) -> Result<(), RtlolaError> {
+        self.submit(inputs, current_time, None)
+    }
+
+    // Like submit_event for (input name, value) pairs, see process_named_event
+    pub fn submit_named_event(&mut self, named: &[(&str, Value)], current_time: Option<Duration>, source: Option<&str>) -> Result<(), RtlolaError> {
+        let inputs = self.named_inputs(named)?;
+        self.submit(inputs, current_time, source)
+    }
+
+    fn submit(&mut self, inputs: Vec<Value>, current_time: Option<Duration>, source: Option<&str>) -> Result<(), RtlolaError> {
+        if inputs.len() != self.input_names.len() {
+            return Err(RtlolaError::InputCount {
+                expected: self.input_names.len(),
+                got: inputs.len(),
+            });
+        }
+        if self.paused {
+            if self.policy.pause_policy == PausePolicy::Buffer {
+                let time = self.resolve_time(current_time)?;
+                self.paused_events.push_back((inputs, time, source.map(str::to_string)));
+            }
+            return Err(RtlolaError::Paused);
+        }
+
+        let elapsed = self.admit_time(self.resolve_time(current_time)?)?;
+        let seen = inputs.iter().map(|value| *value != Value::None).collect::<Vec<_>>();
+        let inputs = self.route_inputs(self.transform_inputs(inputs)?);
+
+        self.monitor.accept_event(inputs, elapsed)
+            .map_err(|e| RtlolaError::AcceptEvent { event: self.stats.events(), time: elapsed, reason: e.to_string() })?;
+        self.last_time = Some(self.last_time.map_or(elapsed, |last| last.max(elapsed)));
+        self.first_time.get_or_insert(elapsed);
+        self.sources.push(self.stats.events() + 1, source);
+        for (seen_input, seen) in self.seen_inputs.iter_mut().zip(seen) {
+            *seen_input |= seen;
+        }
+        self.stats.record_event();
+        Ok(())
+    }
+
+    // Check the time of an event or tick against the input time's range, the out-of-order, duplicate time and
+    // duration policies and make room
+    // for its verdicts. Returns the time the interpreter receives.
+    fn admit_time(&mut self, time: Duration) -> Result<Duration, RtlolaError> {
+        if let Some(limit) = self.monitor.max_time()
+            && time > limit
+        {
+            return Err(RtlolaError::DurationExceeded { limit, got: time });
+        }
+        let elapsed = self.order_time(time)?;
+        if self.last_time == Some(elapsed) {
+            match self.policy.duplicate_time {
+                DuplicateTimePolicy::Allow => {},
+                DuplicateTimePolicy::Warn => log_error!("Warning: event {} has the same time {:?} as the previous event", self.stats.events() + 1, elapsed),
+                DuplicateTimePolicy::Reject => return Err(RtlolaError::DuplicateTimestamp(elapsed)),
+            }
+        }
+        self.make_room()?;
+        if let Some(limit) = self.policy.max_duration
+            && (self.expired || elapsed > limit)
+        {
+            self.expired = true;
+            return Err(RtlolaError::DurationExceeded { limit, got: elapsed });
+        }
+        Ok(elapsed)
+    }
+
+    // Apply the overflow policy until fewer verdicts than the verdict limit are pending
+    fn make_room(&mut self) -> Result<(), RtlolaError> {
+        let Some(limit) = self.policy.verdict_limit else {
+            return Ok(());
+        };
+        let start = Instant::now();
+        while self.receiver.len() >= limit {
+            match self.policy.overflow {
+                OverflowPolicy::Block => {
+                    if self.timeout.is_some_and(|timeout| start.elapsed() >= timeout) {
+                        return Err(RtlolaError::Timeout);
+                    }
+                    thread::sleep(Duration::from_millis(1));
+                },
+                OverflowPolicy::DropOldest => {
+                    // A background consumer may have taken the verdict in the meantime
+                    let Ok(verdict) = self.receiver.try_recv() else {
+                        continue;
+                    };
+                    if self.sources.is_tick_verdict(&verdict) {
+                        continue;
+                    }
+                    self.stats.record(&verdict, self.monitor.ir());
+                    self.sources.take(verdict.kind);
+                    self.dropped_verdicts += 1;
+                },
+            }
+        }
+        Ok(())
+    }
+
+    // Change the verdict limit set by the builder, 0 removes it
+    pub fn set_verdict_limit(&mut self, limit: usize, policy: OverflowPolicy) {
+        self.policy.verdict_limit = (limit > 0).then_some(limit);
+        self.policy.overflow = policy;
+    }
+
+    // Verdicts discarded under OverflowPolicy::DropOldest. Without a verdict limit the queue is unbounded
+    // and no verdict is ever dropped; pending_verdicts shows how far consumers are behind.
+    pub fn dropped_verdicts(&self) -> u64 {
+        self.dropped_verdicts
+    }
+
+    // Register a closure that receives the report of every verdict the process_* methods and tick receive, after the
+    // output filter was applied. Several closures are called in registration order. The background consumer passes
+    // verdicts to its own handler instead.
+    pub fn on_verdict(&mut self, f: impl FnMut(&VerdictReport) + Send + 'static) {
+        self.verdict_callbacks.push(Box::new(f));
+    }
+
+    // Remove every closure registered with on_verdict
+    pub fn clear_verdict_callbacks(&mut self) {
+        self.verdict_callbacks.clear();
+    }
+
+    // Start a thread that passes every verdict to `handler` as soon as the interpreter produces it,
+    // including timed verdicts between events in online mode.
+    // While it runs, events are fed with submit_event; the process_* methods fail with BackgroundRunning.
+    // The handler runs on the background thread.
+    pub fn run_background<F>(&mut self, handler: F) -> Result<(), RtlolaError>
+    where
+        F: FnMut(VerdictReport) + Send + 'static,
+    {
+        if self.background.is_some() {
+            return Err(RtlolaError::BackgroundRunning);
+        }
+        let ir = self.monitor.ir().clone();
+        self.background = Some(Background::spawn(self.receiver.clone(), ir, self.sources.clone(), self.stats.clone(), handler));
+        Ok(())
+    }
+
+    // Stop the background consumer after it delivered the verdicts already queued.
+    // Returns false if none was running.
+    pub fn stop_background(&mut self) -> bool {
+        self.background.take().is_some()
+    }
+
+    // Hand an event to the monitor and receive the next verdict, see receive
+    fn process_queued(&mut self, inputs: Vec<Value>, current_time: Option<Duration>, source: Option<&str>, keep_report: bool) -> Result<Verdict, RtlolaError> {
+        if self.background.is_some() {
+            return Err(RtlolaError::BackgroundRunning);
+        }
+
+        self.submit(inputs, current_time, source)?;
+            
+        let verdict = self.recv_next()?;
+        Ok(self.receive(verdict, source, keep_report))
+    }
+
+    // Record a received verdict in the statistics and apply the output filter. Its report is added to the history,
+    // written to the CSV sink and, if keep_report is set, becomes last_report.
+    fn receive(&mut self, mut verdict: Verdict, source: Option<&str>, keep_report: bool) -> Verdict {
+        self.stats.record(&verdict, self.monitor.ir());
+        if let Some(filter) = &self.selection.filter {
+            verdict.verdict.outputs.retain(|(idx, _)| filter[*idx]);
+        }
+        self.track_changes(&verdict);
+        if !self.selection.edge_modes.is_empty() {
+            self.apply_edge_modes(&mut verdict);
+        }
+        if keep_report || self.history_len > 0 || self.csv_sink.is_some() || !self.verdict_callbacks.is_empty() {
+            let report = VerdictReport::from_queued(verdict.clone(), self.monitor.ir());
+            let report = VerdictReport { changed: self.changed.clone(), ..report };
+            let report = match verdict.kind {
+                VerdictKind::Event => VerdictReport { seq: Some(self.stats.events()), source: source.map(str::to_string), ..report },
+                VerdictKind::Timed => report,
+            };
+            if self.history_len > 0 {
+                if self.history.len() == self.history_len {
+                    self.history.pop_front();
+                }
+                self.history.push_back(report.clone());
+            }
+            // The event was processed and its source taken by the caller, so a failed write does not fail it
+            if let Err(e) = self.write_csv(&report) {
+                log_error!("{}", e);
+            }
+            for callback in &mut self.verdict_callbacks {
+                callback(&report);
+            }
+            if keep_report {
+                self.last_report = Some(report);
+            }
+        }
+        verdict
+    }
+
+    // Compare the output values of a verdict with the latest values and remember them
+    fn track_changes(&mut self, verdict: &Verdict) {
+        self.changed.clear();
+        let ir = self.monitor.ir();
+        for (idx, changes) in &verdict.verdict.outputs {
+            let OutputKind::NamedOutput(name) = &ir.outputs[*idx].kind else {
+                continue;
+            };
+            for change in changes {
+                if let Change::Value(_, value) = change {
+                    let previous = self.output_values.insert(name.clone(), value.clone()).unwrap_or(Value::None);
+                    if previous != *value {
+                        self.changed.push((name.clone(), previous, value.clone()));
+                    }
+                }
+            }
+        }
+    }
+
+    // Named outputs whose value changed in the latest verdict received by a process_* method or tick,
+    // as (name, previous, current). Value::None stands for an output's first value. Instances of a parameterized
+    // output are compared with the latest value of any instance.
+    pub fn changed_outputs(&self) -> Vec<(String, Value, Value)> {
+        self.changed.clone()
+    }
+
+    // The value a named output holds after the latest verdict received by a process_* method or tick, kept across
+    // verdicts in which the output did not change. Where changed_outputs reports the edges, this is the level.
+    // None for unknown names, outputs without a value yet and outputs excluded by the output filter.
+    pub fn current_output(&self, name: &str) -> Option<Value> {
+        self.output_values.get(name).cloned()
+    }
+
+    // Indices of the triggers that have not fired in this run yet, in index order. Together with fired_triggers
+    // this shows which conditions a test run exercised.
+    pub fn dormant_triggers(&self) -> Vec<usize> {
+        self.stats.trigger_counts().into_iter().filter(|(_, count)| *count == 0).map(|(idx, _)| idx).collect()
+    }
+
+    // Indices of the triggers that fired at least once in this run, in index order
+    pub fn fired_triggers(&self) -> Vec<usize> {
+        self.stats.trigger_counts().into_iter().filter(|(_, count)| *count > 0).map(|(idx, _)| idx).collect()
+    }
+
+    // Advance the monitor to `now` seconds without new input values and return the reports of the timed verdicts
+    // that became ready, for host loops that call tick on a timer and process_event when inputs arrive.
+    // In offline mode the interpreter receives an event without input values at `now`, which evaluates the periodic
+    // streams due before then; it is not counted as an event and its own verdict is not returned. The interpreter
+    // reports nothing once the deadlines are done, so tick waits for the number of deadlines the spec's schedule has
+    // before `now`; specs with locally periodic streams have a dynamic schedule and fail with ModeIncompatible.
+    // Like an event, the tick is subject to the out-of-order, duplicate time and duration policies, and fails with
+    // Paused while the monitor is paused and with InvalidTime for a negative, NaN or infinite `now`.
+    // In online mode the interpreter evaluates periodic streams on its own, `now` is ignored and tick only
+    // collects the verdicts that are ready without waiting.
+    pub fn tick(&mut self, now: f64) -> Result<Vec<VerdictReport>, RtlolaError> {
+        Ok(self.tick_verdicts(now)?.into_iter().map(|(_, report)| report).collect())
+    }
+
+    // Number of static deadlines in [from, to), which the interpreter evaluates when an event at `to` follows one at `from`
+    fn static_deadlines(&self, from: Duration, to: Duration) -> Result<usize, RtlolaError> {
+        let ir = self.monitor.ir();
+        if ir.outputs.iter().any(|output| matches!(output.eval.eval_pacing, PacingType::LocalPeriodic(_))) {
+            return Err(RtlolaError::ModeIncompatible {
+                reason: "tick cannot predict the deadlines of locally periodic streams in offline mode".to_string(),
+            });
+        }
+        let Ok(schedule) = ir.compute_schedule() else {
+            return Ok(0);
+        };
+        let Some(hyper_period) = schedule.hyper_period.filter(|period| !period.is_zero()) else {
+            return Ok(0);
+        };
+        // The schedule repeats every hyper period starting at 0, skip the periods that end before `from`.
+        // A period's last deadline is at its end, so the period ending at `from` is not skipped.
+        let skipped = (from.as_nanos() / hyper_period.as_nanos()).saturating_sub(1);
+        let mut time = Duration::from_nanos((skipped * hyper_period.as_nanos()) as u64);
+        let mut due = 0;
+        for deadline in schedule.deadlines.iter().cycle() {
+            time += deadline.pause;
+            if time >= to {
+                break;
+            }
+            if time >= from {
+                due += 1;
+            }
+        }
+        Ok(due)
+    }
+
+    // Like tick, formatting the verdicts in the monitor's style
+    pub(crate) fn tick_str(&mut self, now: f64) -> Result<String, RtlolaError> {
+        let verdicts = self.tick_verdicts(now)?;
+        Ok(self.format_verdicts(verdicts))
+    }
+
+    // Format verdicts that were already received in the verdict style
+    fn format_verdicts(&self, verdicts: Vec<(Verdict, VerdictReport)>) -> String {
+        let mut out = String::new();
+        for (verdict, report) in verdicts {
+            let ts = self.display_time(verdict.ts);
+            let written = match self.format.style {
+                VerdictStyle::Lines => self.write_verdict(&mut out, verdict, ts, None, None),
+                VerdictStyle::Table => self.write_table(&mut out, &report, ts),
+                VerdictStyle::Logfmt => self.write_logfmt(&mut out, &report, ts),
+            };
+            written.expect("writing to a String cannot fail");
+        }
+        out
+    }
+
+    fn tick_verdicts(&mut self, now: f64) -> Result<Vec<(Verdict, VerdictReport)>, RtlolaError> {
+        if self.background.is_some() {
+            return Err(RtlolaError::BackgroundRunning);
+        }
+        let mut verdicts = Vec::new();
+        match self.mode() {
+            MonitorMode::Offline => {
+                // A tick is not an event and is never buffered
+                if self.paused {
+                    return Err(RtlolaError::Paused);
+                }
+                let from = self.last_time.unwrap_or_default();
+                let now = self.admit_time(event_time(now)?)?;
+                let due = self.static_deadlines(from, now)?;
+                let inputs = vec![Value::None; self.monitor.ir().inputs.len()];
+                self.monitor.accept_event(inputs, now)
+                    .map_err(|e| RtlolaError::AcceptEvent { event: self.stats.events(), time: now, reason: e.to_string() })?;
+                self.last_time = Some(self.last_time.map_or(now, |last| last.max(now)));
+                self.sources.push_tick(now);
+                // Every deadline before now has a verdict, the tick's own verdict is skipped whenever it arrives
+                while verdicts.len() < due {
+                    verdicts.push(self.recv_next()?);
+                }
+            },
+            MonitorMode::Online => verdicts.extend(self.receiver.try_iter()),
+        }
+        Ok(verdicts.into_iter()
+            .map(|verdict| {
+                let verdict = self.receive(verdict, None, true);
+                (verdict, self.last_report.clone().expect("receive keeps the report"))
+            })
+            .collect())
+    }
+
+    // Take the verdicts that are already queued without waiting for more, as reports
+    pub(crate) fn queued_reports(&mut self) -> Vec<VerdictReport> {
+        self.queued_verdicts().into_iter().map(|(_, report)| report).collect()
+    }
+
+    fn queued_verdicts(&mut self) -> Vec<(Verdict, VerdictReport)> {
+        let verdicts = self.receiver.try_iter().filter(|verdict| !self.sources.is_tick_verdict(verdict)).collect::<Vec<_>>();
+        verdicts.into_iter()
+            .map(|verdict| {
+                let verdict = self.receive(verdict, None, true);
+                self.sources.take(verdict.kind);
+                (verdict, self.last_report.clone().expect("receive keeps the report"))
+            })
+            .collect()
+    }
+
+    // Wait until the interpreter evaluated every accepted event. An unstarted interpreter for the same spec takes
+    // its place, which does not spawn a thread, so the monitor stays usable for inspection.
+    fn end_run(&mut self) {
+        let ir = self.monitor.ir().clone();
+        let num_inputs = ir.inputs.len();
+        let fresh = Backend::new(ir, self.monitor.mode(), self.monitor.input_time(), num_inputs);
+        if let Err(e) = std::mem::replace(&mut self.monitor, fresh).end() {
+            log_error!("Failed to end monitor: {:?}", e);
+        }
+    }
+
+    // End the run: wait until every accepted event is evaluated, fold the verdicts nobody consumed yet
+    // into the statistics and return them.
+    pub fn finalize(mut self) -> RunSummary {
+        // Delivers the verdicts already queued to the handler
+        self.stop_background();
+        self.end_run();
+        let ir = self.monitor.ir().clone();
+        for verdict in self.receiver.try_iter().filter(|verdict| !self.sources.is_tick_verdict(verdict)) {
+            self.stats.record(&verdict, &ir);
+        }
+        self.stats.snapshot()
+    }
+
+    // If set, dropping a started monitor ends the run and writes the verdicts nobody consumed, typically the timed
+    // verdicts after the last processed event, to the sink in the verdict style, or logs them without a sink.
+    // Off by default: dropping then blocks until the interpreter evaluated every accepted event, and a failed
+    // write can only be logged.
+    pub fn set_flush_on_drop(&mut self, flush: bool) {
+        self.flush_on_drop = flush;
+    }
+
+    pub fn flush_on_drop(&self) -> bool {
+        self.flush_on_drop
+    }
+
+    // Apply the out-of-order policy to an event time
+    fn order_time(&self, time: Duration) -> Result<Duration, RtlolaError> {
+        match (self.last_time, self.policy.out_of_order) {
+            (Some(prev), OutOfOrderPolicy::Reject) if time < prev => Err(RtlolaError::TimeRegression { prev, got: time }),
+            (Some(prev), OutOfOrderPolicy::Clamp) => Ok(time.max(prev)),
+            _ => Ok(time),
+        }
+    }
+
+    // Use the given time, or read the clock if there is one
+    pub(crate) fn resolve_time(&self, current_time: Option<Duration>) -> Result<Duration, RtlolaError> {
+        match (current_time, &self.clock) {
+            (Some(time), _) => Ok(time),
+            (None, Some(clock)) => Ok(clock.now()),
+            (None, None) => Err(RtlolaError::MissingTime),
+        }
+    }
+
+    // Process an event and return a formatted string with the verdict
+    pub fn process_event_verdict(&mut self, inputs: Vec<Value>, current_time: Option<Duration>) -> Result<String, RtlolaError> {
+        self.process_event_verdict_str(inputs, current_time).map(str::to_owned)
+    }
+
+    // Like process_event_verdict with the time in seconds, for callers whose timestamps are floats.
+    // A negative, NaN or infinite time is rejected with RtlolaError::InvalidTime.
+    pub fn process_event_verdict_secs(&mut self, inputs: Vec<Value>, current_time: Option<f64>) -> Result<String, RtlolaError> {
+        let current_time = current_time.map(event_time).transpose()?;
+        self.process_event_verdict(inputs, current_time)
+    }
+
+    // Like process_event_verdict, but formats into a buffer owned by the monitor that is reused across events.
+    // The returned string is only valid until the next call.
+    pub fn process_event_verdict_str(&mut self, inputs: Vec<Value>, current_time: Option<Duration>) -> Result<&str, RtlolaError> {
+        self.format_event(inputs, current_time, None)
+    }
+
+    // Like process_named_event, returning the formatted verdict; the source label is shown as [source=...]
+    pub fn process_named_event_verdict(&mut self, named: &[(&str, Value)], current_time: Option<Duration>, source: Option<&str>) -> Result<String, RtlolaError> {
+        let inputs = self.named_inputs(named)?;
+        self.format_event(inputs, current_time, source).map(str::to_owned)
+    }
+
+    // Set the destination process_event_to_sink writes formatted verdicts to, e.g. a file or socket
+    pub fn set_sink(&mut self, sink: Box<dyn io::Write + Send>) {
+        self.sink = Some(sink);
+    }
+
+    // Remove the sink and hand it back
+    pub fn take_sink(&mut self) -> Option<Box<dyn io::Write + Send>> {
+        self.sink.take()
+    }
+
+    // Like process_event_verdict_str, but writes the verdict into the sink and flushes it instead of returning it
+    pub fn process_event_to_sink(&mut self, inputs: Vec<Value>, current_time: Option<Duration>) -> Result<(), RtlolaError> {
+        let mut sink = self.sink.take().ok_or(RtlolaError::NoSink)?;
+        let result = self.format_event(inputs, current_time, None).and_then(|verdict| {
+            sink.write_all(verdict.as_bytes())
+                .and_then(|()| sink.flush())
+                .map_err(RtlolaError::Sink)
+        });
+        self.sink = Some(sink);
+        result
+    }
+
+    fn format_event(&mut self, inputs: Vec<Value>, current_time: Option<Duration>, source: Option<&str>) -> Result<&str, RtlolaError> {
+        let elapsed = self.order_time(self.resolve_time(current_time)?)?;
+        let mut verdict = self.process_queued(inputs, Some(elapsed), source, true)?;
+        let (seq, source) = self.sources.take(verdict.kind);
+        let seq = seq.filter(|_| self.format.show_seq);
+        self.echo_filter(&mut verdict);
+
+        let mut buffer = std::mem::take(&mut self.output_buffer);
+        buffer.clear();
+        let unchanged = verdict.verdict.outputs.iter().all(|(_, changes)| changes.is_empty());
+        if self.format.quiet_unchanged && unchanged {
+            self.output_buffer = buffer;
+            return Ok(&self.output_buffer);
+        }
+        let time = self.display_time(elapsed);
+        let written = match self.format.style {
+            VerdictStyle::Lines => self.write_verdict(&mut buffer, verdict, time, seq, source.as_deref()),
+            VerdictStyle::Table => {
+                let report = self.last_report.as_ref().expect("process_queued keeps the report");
+                self.write_table(&mut buffer, &VerdictReport { seq, ..report.clone() }, time)
+            },
+            VerdictStyle::Logfmt => {
+                let report = self.last_report.as_ref().expect("process_queued keeps the report");
+                self.write_logfmt(&mut buffer, &VerdictReport { seq, ..report.clone() }, time)
+            },
+        };
+        written.expect("writing to a String cannot fail");
+        self.output_buffer = buffer;
+        Ok(&self.output_buffer)
+    }
+
+    // Drop the inputs the echo setting excludes and remember the event's inputs for the next comparison
+    fn echo_filter(&mut self, verdict: &mut Verdict) {
+        if self.last_inputs.is_empty() {
+            self.last_inputs = vec![Value::None; self.monitor.ir().inputs.len()];
+        }
+        if matches!(verdict.kind, VerdictKind::Timed) {
+            if self.format.echo_timed_inputs && self.format.echo_inputs == EchoInputs::All {
+                verdict.verdict.inputs = self.last_inputs.iter()
+                    .enumerate()
+                    .filter(|(_, val)| **val != Value::None)
+                    .map(|(idx, val)| (idx, val.clone()))
+                    .collect();
+            }
+            return;
+        }
+        let inputs = std::mem::take(&mut verdict.verdict.inputs);
+        for (idx, val) in inputs {
+            let changed = self.last_inputs[idx] != val;
+            self.last_inputs[idx] = val.clone();
+            let echo = match self.format.echo_inputs {
+                EchoInputs::All => true,
+                EchoInputs::Changed => changed,
+                EchoInputs::None => false,
+            };
+            if echo {
+                verdict.verdict.inputs.push((idx, val));
+            }
+        }
+    }
+
+    // Write the verdict with color codes, one line per input, output and trigger change
+    fn write_verdict(&self, out: &mut String, verdict: Verdict, ts: Seconds, seq: Option<u64>, source: Option<&str>) -> fmt::Result {
+        let ir = self.monitor.ir();
+
+        write!(out, "[{}s] ", ts)?;
+        if let Some(seq) = seq {
+            write!(out, "[#{}] ", seq)?;
+        }
+        match (verdict.kind, source) {
+            (VerdictKind::Timed, _) => {
+                let changes = verdict.verdict.outputs.iter().map(|(_, changes)| changes.len()).sum();
+                writeln!(out, "{}", deadline_text(changes))?;
+            },
+            (VerdictKind::Event, Some(source)) => writeln!(out, "Processing new event [source={}]", source)?,
+            (VerdictKind::Event, None) => writeln!(out, "Processing new event")?,
+        }
+
+        for (idx, val) in verdict.verdict.inputs {
+            let input = &ir.inputs[idx];
+            writeln!(
+                out,
+                "[{}s] {} {} = {}",
+                ts,
+                self.format.paint("[Input]", self.format.theme.input),
+                self.format.paint(&format!("[{}]", input.name), self.format.theme.input),
+                self.typed(self.format_number(val, Self::DEFAULT_THRESHOLD), &input.ty)
+            )?;
+        }
+    
+        for (out_idx, changes) in verdict.verdict.outputs {
+            let output = &ir.outputs[out_idx];
+            let name = match &output.kind {
+                OutputKind::NamedOutput(name) => {
+                    self.format.paint(&format!("[Output][{}]", name), self.format.theme.output)
+                },
+                OutputKind::Trigger(trigger_idx) => {
+                    self.format.paint(&format!("[#{}]", trigger_idx), self.format.theme.trigger)
+                },
+            };
+    
+            for change in changes {
+                match change {
+                    Change::Spawn(param) => {
+                        writeln!(out, "[{}s] {} {} {}", ts, name, self.format.paint("[Spawn]", self.format.theme.spawn), self.format_params(&param))?;
+                    },
+                    Change::Value(_, val) => match output.kind {
+                        OutputKind::NamedOutput(ref output_name) => {
+                            writeln!(
+                                out,
+                                "[{}s] {} {} {}",
+                                ts,
+                                name,
+                                self.format.paint("[Value] = ", self.format.theme.value),
+                                self.typed(self.format_output(output_name, val), &output.ty)
+                            )?;
+                        },
+                        OutputKind::Trigger(_) => {
+                            writeln!(out, "[{}s] {} {} {}", ts, self.format.paint("[Trigger]", self.format.theme.trigger), name, Self::trigger_text(output, val))?;
+                        },
+                    },
+                    Change::Close(param) => {
+                        writeln!(out, "[{}s] {} {} {}", ts, name, self.format.paint("[Close]", self.format.theme.close), self.format_params(&param))?;
+                    },
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // The spec's inputs as a comma-separated name:type list in declaration order,
+    // e.g. "height:Float64,velocity:Float64,temperature:Float64"
+    pub fn input_schema(&self) -> String {
+        self.monitor.ir().inputs.iter()
+            .map(|input| format!("{}:{}", input.name, input.ty))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    // Names of the named outputs in spec order, excluding triggers
+    pub fn outputs(&self) -> Vec<String> {
+        self.monitor.ir().outputs.iter()
+            .filter(|output| matches!(output.kind, OutputKind::NamedOutput(_)))
+            .map(|output| output.name.clone())
+            .collect()
+    }
+
+    // The latest value of a named output in this run, None if it has not produced a value yet
+    pub fn last_output_value(&self, name: &str) -> Option<Value> {
+        self.stats.last_value(name)
+    }
+
+    // Verdicts produced by the interpreter that were not consumed yet
+    pub fn pending_verdicts(&self) -> usize {
+        self.receiver.len()
+    }
+
+    // Rough time until the pending verdicts are consumed at the throughput measured so far in this run.
+    // None if verdicts are pending but fewer than two were consumed yet, or if the estimate is too large for a Duration.
+    pub fn estimated_drain(&self) -> Option<Duration> {
+        let pending = self.pending_verdicts();
+        if pending == 0 {
+            return Some(Duration::ZERO);
+        }
+        let per_second = self.stats.throughput()?;
+        Duration::try_from_secs_f64(pending as f64 / per_second).ok()
+    }
+
+    // The instances of a parameterized output that were spawned and not closed yet, as (parameters, latest value).
+    // An instance that has not produced a value yet has Value::None. The order is unspecified.
+    pub fn active_instances(&self, output_name: &str) -> Vec<(Vec<Value>, Value)> {
+        match self.monitor.ir().outputs.iter().position(|output| output.name == output_name) {
+            Some(idx) => self.stats.instances(idx),
+            None => Vec::new(),
+        }
+    }
+
+    // How a named output or trigger is paced, None if the spec has no output of that name
+    pub fn output_pacing(&self, name: &str) -> Option<Pacing> {
+        let ir = self.monitor.ir();
+        let output = ir.outputs.iter().find(|output| output.name == name)?;
+        let clauses = output.eval.clauses.iter().map(|clause| &clause.pacing).collect::<Vec<_>>();
+        if clauses.iter().skip(1).any(|pacing| *pacing != clauses[0]) {
+            return Some(Pacing::Mixed(clauses.into_iter().map(|pacing| Self::pacing(ir, output, pacing)).collect()));
+        }
+        Some(Self::pacing(ir, output, &output.eval.eval_pacing))
+    }
+
+    fn pacing(ir: &RtLolaMir, output: &OutputStream, pacing: &PacingType) -> Pacing {
+        let period = |frequency, locality| {
+            TimeDrivenStream { reference: output.reference, frequency, locality }.period_in_duration()
+        };
+        match pacing {
+            PacingType::GlobalPeriodic(frequency) => Pacing::Periodic(period(*frequency, PacingLocality::Global)),
+            PacingType::LocalPeriodic(frequency) => Pacing::LocalPeriodic(period(*frequency, PacingLocality::Local)),
+            PacingType::Event(condition) => Pacing::Event(Self::activation_condition(ir, condition)),
+            PacingType::Constant => Pacing::Constant,
+        }
+    }
+
+    fn activation_condition(ir: &RtLolaMir, condition: &ActivationCondition) -> String {
+        let join = |conditions: &[ActivationCondition], op: &str| {
+            let parts = conditions.iter().map(|c| Self::activation_condition(ir, c)).collect::<Vec<_>>();
+            format!("({})", parts.join(op))
+        };
+        match condition {
+            ActivationCondition::Conjunction(conditions) => join(conditions, " & "),
+            ActivationCondition::Disjunction(conditions) => join(conditions, " | "),
+            ActivationCondition::Stream(reference) => Self::stream_name(ir, *reference).to_string(),
+            ActivationCondition::True => "true".to_string(),
+        }
+    }
+
+    fn stream_name(ir: &RtLolaMir, reference: StreamReference) -> &str {
+        match reference {
+            StreamReference::In(idx) => &ir.inputs[idx].name,
+            StreamReference::Out(idx) => &ir.outputs[idx].name,
+        }
+    }
+
+    // The stream dependency graph in Graphviz DOT format, render it with `dot -Tpng`.
+    // Inputs are boxes, outputs ellipses and triggers octagons labeled with their message.
+    // Edges point from the accessed stream to the accessing one and are labeled with non-synchronous access kinds.
+    pub fn dependency_dot(&self) -> String {
+        let ir = self.monitor.ir();
+        let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+        let mut dot = String::from("digraph spec {\n    rankdir=LR;\n");
+
+        for input in &ir.inputs {
+            let _ = writeln!(dot, "    {} [shape=box];", quote(&input.name));
+        }
+        for output in &ir.outputs {
+            let _ = match output.kind {
+                OutputKind::NamedOutput(_) => writeln!(dot, "    {} [shape=ellipse];", quote(&output.name)),
+                OutputKind::Trigger(idx) => writeln!(
+                    dot,
+                    "    {} [shape=octagon, label={}];",
+                    quote(&output.name),
+                    quote(&format!("#{}: {}", idx, Self::trigger_message(output)))
+                ),
+            };
+        }
+        for output in &ir.outputs {
+            for (source, accesses) in &output.accesses {
+                let mut labels = Vec::new();
+                for (_, kind) in accesses {
+                    let label = match kind {
+                        StreamAccessKind::Sync => continue,
+                        StreamAccessKind::Hold => "hold".to_string(),
+                        StreamAccessKind::Offset(Offset::Past(n)) => format!("offset(-{})", n),
+                        StreamAccessKind::Offset(Offset::Future(n)) => format!("offset(+{})", n),
+                        StreamAccessKind::SlidingWindow(_) => "sliding window".to_string(),
+                        StreamAccessKind::DiscreteWindow(_) => "discrete window".to_string(),
+                        StreamAccessKind::InstanceAggregation(_) => "aggregation".to_string(),
+                        StreamAccessKind::Get => "get".to_string(),
+                        StreamAccessKind::Fresh => "fresh".to_string(),
+                    };
+                    if !labels.contains(&label) {
+                        labels.push(label);
+                    }
+                }
+                let source = quote(Self::stream_name(ir, *source));
+                let _ = match labels.is_empty() {
+                    true => writeln!(dot, "    {} -> {};", source, quote(&output.name)),
+                    false => writeln!(dot, "    {} -> {} [label={}];", source, quote(&output.name), quote(&labels.join(", "))),
+                };
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    // Stream names grouped by evaluation layer, lowest first, each in spec order with inputs before outputs.
+    // Within one event a stream is evaluated after every stream in lower layers, so a synchronous access
+    // always reads a value computed for the same event.
+    pub fn evaluation_layers(&self) -> Vec<Vec<String>> {
+        let ir = self.monitor.ir();
+        let mut layers = BTreeMap::<usize, Vec<String>>::new();
+        let streams = ir.inputs.iter().map(|input| (input.eval_layer(), input.name()))
+            .chain(ir.outputs.iter().map(|output| (output.eval_layer(), output.name())));
+        for (layer, name) in streams {
+            layers.entry(layer.into()).or_default().push(name.to_string());
+        }
+        layers.into_values().collect()
+    }
+
+    // Inputs of the spec that no output reads or is paced by, in spec order. Values given for them are ignored,
+    // which usually points to a leftover declaration or a typo in the stream that was meant to read them.
+    pub fn unused_inputs(&self) -> Vec<String> {
+        let ir = self.monitor.ir();
+        let mut used = ir.inputs.iter()
+            .map(|input| !input.accessed_by.is_empty() || !input.aggregated_by.is_empty())
+            .collect::<Vec<_>>();
+        for output in &ir.outputs {
+            let clauses = output.eval.clauses.iter().map(|clause| &clause.pacing);
+            for pacing in clauses.chain([&output.eval.eval_pacing, &output.spawn.pacing, &output.close.pacing]) {
+                if let PacingType::Event(condition) = pacing {
+                    Self::mark_inputs(condition, &mut used);
+                }
+            }
+        }
+        ir.inputs.iter().zip(used)
+            .filter(|(_, used)| !used)
+            .map(|(input, _)| input.name.clone())
+            .collect()
+    }
+
+    fn mark_inputs(condition: &ActivationCondition, used: &mut [bool]) {
+        match condition {
+            ActivationCondition::Conjunction(conditions) | ActivationCondition::Disjunction(conditions) => {
+                conditions.iter().for_each(|condition| Self::mark_inputs(condition, used));
+            },
+            ActivationCondition::Stream(StreamReference::In(idx)) => used[*idx] = true,
+            ActivationCondition::Stream(StreamReference::Out(_)) | ActivationCondition::True => {},
+        }
+    }
+
+    // Period of the fastest periodic stream, or None if the spec has no periodic streams.
+    // A host driving the monitor in real time should deliver events or timer ticks at least this often.
+    pub fn min_period(&self) -> Option<Duration> {
+        self.monitor.ir().time_driven.iter()
+            .map(|stream| stream.period_in_duration())
+            .min()
+    }
+
+    // All triggers of the spec as (trigger index, message) pairs
+    pub fn triggers(&self) -> Vec<(usize, String)> {
+        self.monitor.ir().outputs.iter()
+            .filter_map(|output| match output.kind {
+                OutputKind::Trigger(idx) => Some((idx, Self::trigger_message(output))),
+                OutputKind::NamedOutput(_) => None,
+            })
+            .collect()
+    }
+
+    // The constant message of a trigger, or its stream name if the message is computed
+    fn trigger_message(output: &OutputStream) -> String {
+        match output.eval.clauses.first().map(|clause| &clause.expression.kind) {
+            Some(ExpressionKind::LoadConstant(Constant::Str(msg))) => msg.clone(),
+            _ => output.name.clone(),
+        }
+    }
+
+    // A trigger with a constant message is shown as just its message, or its name if the message is empty.
+    // Any other trigger carries a meaningful value and is shown as `message = value`.
+    pub(crate) fn trigger_text(output: &OutputStream, val: Value) -> String {
+        match output.eval.clauses.first().map(|clause| &clause.expression.kind) {
+            Some(ExpressionKind::LoadConstant(_)) => match val {
+                Value::Str(msg) if !msg.is_empty() => msg.to_string(),
+                Value::Bool(true) | Value::Str(_) => output.name.clone(),
+                val => val.to_string(),
+            },
+            _ => format!("{} = {}", Self::trigger_message(output), val),
+        }
+    }
+
+    pub fn format_number(&self, val: Value, threshold: f64) -> String {
+        match val {
+            Value::Float(f) => {
+                let threshold = threshold.abs();
+                if f.abs() <= self.format.zero_epsilon {
+                    format!("{:.1}", 0.0)
+                } else if f.abs() < threshold {
+                    format!("{:.*e}", self.format.precision, f.into_inner())
+                } else {
+                    format!("{:.*}", self.format.precision, f)
+                }
+            },
+            // Other variants remain the same
+            _ => val.to_string(),
+        }
+    }
+
+}
+// Whether name matches a glob pattern with the wildcards `*` and `?`.
+// On a mismatch after a `*`, the star absorbs one more character and matching resumes.
+fn glob_match(pattern: &[char], name: &[char]) -> bool {
+    let (mut p, mut n) = (0, 0);
+    let mut star = None; // Position of the last `*` and of the name character it absorbs up to
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            },
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            },
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    star = Some((star_p, star_n + 1));
+                    p = star_p + 1;
+                    n = star_n + 1;
+                },
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+impl Drop for RtlolaMonitor {
+    fn drop(&mut self) {
+        if !self.flush_on_drop || !self.started {
+            return;
+        }
+        // A background consumer hands the queued verdicts to its handler when it stops
+        self.stop_background();
+        self.end_run();
+        let verdicts = self.queued_verdicts();
+        let out = self.format_verdicts(verdicts);
+        if out.is_empty() {
+            return;
+        }
+        match &mut self.sink {
+            Some(sink) => {
+                if let Err(e) = sink.write_all(out.as_bytes()).and_then(|()| sink.flush()) {
+                    log_error!("{}", RtlolaError::Sink(e));
+                }
+            },
+            None => log_error!("{}", out.trim_end()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::ColorChoice;
+    use crate::test_util::*;
+
+    #[test]
+    fn finalize_aggregates_the_run() {
+        let mut monitor = ball_monitor();
+        monitor.process_event(ball_event(1.0, -2.0, 20.0), Some(Duration::from_secs(1))).unwrap();
+        monitor.process_event(ball_event(0.0, 3.0, 40.0), Some(Duration::from_secs(2))).unwrap();
+        let summary = monitor.finalize();
+        assert_eq!(summary.events, 2);
+        assert_eq!(summary.duration, Duration::from_secs(2));
+        // falling, rising, hit the ground (also close to it) and hot fired once each
+        assert_eq!(summary.trigger_counts, BTreeMap::from([(0, 1), (1, 1), (2, 1), (3, 1), (4, 1)]));
+        let fahrenheit = &summary.outputs["hotInFar"];
+        assert_eq!(fahrenheit.updates, 2);
+        assert_eq!(fahrenheit.min, Some(make_float(68.0).unwrap()));
+        assert_eq!(fahrenheit.max, Some(make_float(104.0).unwrap()));
+        assert_eq!(fahrenheit.last, make_float(104.0).unwrap());
+    }
+
+    #[test]
+    fn run_trace_rejects_invalid_times() {
+        let mut monitor = ball_monitor();
+        for time in [-1.0, f64::NAN, f64::INFINITY] {
+            let result = monitor.run_trace([(ball_event(1.0, 0.0, 20.0), time)]);
+            assert!(matches!(result, Err(RtlolaError::InvalidTime(_))), "time {}", time);
+        }
+    }
+
+    #[test]
+    fn resilient_trace_skips_invalid_times() {
+        let mut monitor = ball_monitor();
+        let events = [(ball_event(1.0, 0.0, 20.0), 1.0), (ball_event(1.0, 0.0, 20.0), f64::NAN), (ball_event(1.0, 0.0, 20.0), 2.0)];
+        let results = monitor.process_trace_resilient(events);
+        assert!(results[0].is_ok());
+        assert!(matches!(results[1], Err(RtlolaError::InvalidTime(_))));
+        assert_eq!(results[2].as_ref().unwrap().time, Duration::from_secs(2));
+    }
+
+    #[test]
+    fn verdict_secs_rejects_invalid_times() {
+        let mut monitor = ball_monitor();
+        let result = monitor.process_event_verdict_secs(ball_event(1.0, 0.0, 20.0), Some(-0.5));
+        assert!(matches!(result, Err(RtlolaError::InvalidTime(_))));
+        assert!(monitor.process_event_verdict_secs(ball_event(1.0, 0.0, 20.0), Some(0.5)).is_ok());
+    }
+
+    #[test]
+    fn triggers_come_from_the_verdict() {
+        let mut monitor = ball_monitor();
+        let fired = monitor.process_event_triggers(ball_event(0.1, -1.0, 40.0), Some(Duration::from_secs(1))).unwrap();
+        let indices = fired.iter().map(|(idx, _)| *idx).collect::<Vec<_>>();
+        assert_eq!(indices, [0, 2, 4]);
+        assert_eq!(fired[0].1, Value::Str("Ball is falling".into()));
+        let report = monitor.last_report().unwrap();
+        assert_eq!(report.time, Duration::from_secs(1));
+        assert_eq!(report.triggers.iter().map(|trigger| trigger.index).collect::<Vec<_>>(), [0, 2, 4]);
+        assert!(monitor.process_event_violated(ball_event(1.0, 1.0, 20.0), Some(Duration::from_secs(2))).unwrap());
+        assert_eq!(monitor.last_report().unwrap().time, Duration::from_secs(2));
+    }
+
+    #[test]
+    fn replay_rejects_invalid_times() {
+        let mut monitor = ball_monitor();
+        let events = [(ball_event(1.0, 0.0, 20.0), 0.01), (ball_event(1.0, 0.0, 20.0), f64::NAN)];
+        assert!(matches!(monitor.replay_realtime(events, 1.0), Err(RtlolaError::InvalidTime(_))));
+        // The event before the invalid one was processed
+        assert_eq!(monitor.last_report().unwrap().time, Duration::from_millis(10));
+    }
+
+    #[test]
+    fn for_each_verdict_stops_at_invalid_times() {
+        let mut monitor = ball_monitor();
+        let events = [(ball_event(1.0, 0.0, 20.0), 1.0), (ball_event(1.0, 0.0, 20.0), -1.0), (ball_event(1.0, 0.0, 20.0), 2.0)];
+        let mut times = Vec::new();
+        let result = monitor.for_each_verdict(events, |report| times.push(report.time));
+        assert!(matches!(result, Err(RtlolaError::InvalidTime(_))));
+        assert_eq!(times, [Duration::from_secs(1)]);
+    }
+
+    const PERIODIC: &str = "input x: Float64\noutput p @1Hz := x.hold().defaults(to: 0.0) * 10.0";
+
+    #[test]
+    fn tick_returns_the_deadlines_before_now() {
+        let mut monitor = spec_monitor(PERIODIC, &["x"]);
+        monitor.process_event(vec![make_float(1.0).unwrap()], Some(Duration::from_millis(500))).unwrap();
+        let reports = monitor.tick(3.5).unwrap();
+        let times = reports.iter().map(|report| report.time).collect::<Vec<_>>();
+        assert_eq!(times, [1, 2, 3].map(Duration::from_secs));
+        assert!(reports.iter().all(|report| report.kind == VerdictKind::Timed));
+        assert_eq!(monitor.current_output("p"), Some(make_float(10.0).unwrap()));
+    }
+
+    #[test]
+    fn mock_clock_times_events_and_deadlines() {
+        let clock = crate::clock::MockClock::new();
+        let mut monitor = RtlolaMonitor::builder("", &["x"]).spec_text(PERIODIC).timeout_ms(500).clock(clock.clone()).build().unwrap();
+        monitor.start().unwrap();
+
+        clock.set(Duration::from_millis(500));
+        let report = monitor.process_event(vec![make_float(1.0).unwrap()], None).unwrap();
+        assert_eq!((report.kind, report.time), (VerdictKind::Event, Duration::from_millis(500)));
+
+        // The next event is stamped 2.5s, after the deadlines at 1s and 2s, which come first
+        clock.advance(Duration::from_secs(2));
+        let report = monitor.process_event(vec![make_float(2.0).unwrap()], None).unwrap();
+        assert_eq!((report.kind, report.time), (VerdictKind::Timed, Duration::from_secs(1)));
+        assert_eq!(monitor.current_output("p"), Some(make_float(10.0).unwrap()));
+        let rest = [monitor.recv_next().unwrap(), monitor.recv_next().unwrap()];
+        let rest = rest.map(|verdict| (verdict.kind, verdict.ts));
+        assert_eq!(rest, [(VerdictKind::Timed, Duration::from_secs(2)), (VerdictKind::Event, Duration::from_millis(2500))]);
+    }
+
+    #[test]
+    fn tick_skips_its_own_verdict() {
+        // c is evaluated on every event, so the tick's event has a verdict of its own
+        let spec = format!("{}\noutput c @true := 1.0", PERIODIC);
+        let mut monitor = spec_monitor(&spec, &["x"]);
+        monitor.process_event(vec![make_float(1.0).unwrap()], Some(Duration::from_millis(500))).unwrap();
+        assert_eq!(monitor.tick(1.5).unwrap().len(), 1);
+        let report = monitor.process_event(vec![make_float(2.0).unwrap()], Some(Duration::from_millis(1700))).unwrap();
+        assert_eq!(report.time, Duration::from_millis(1700));
+        assert_eq!(report.kind, VerdictKind::Event);
+        assert_eq!(report.seq, Some(2));
+    }
+
+    #[test]
+    fn tick_without_own_verdict_keeps_the_next_event() {
+        let mut monitor = spec_monitor(PERIODIC, &["x"]);
+        monitor.tick(0.5).unwrap();
+        let report = monitor.process_event(vec![make_float(2.0).unwrap()], Some(Duration::from_millis(700))).unwrap();
+        assert_eq!((report.time, report.seq), (Duration::from_millis(700), Some(1)));
+    }
+
+    #[test]
+    fn tick_applies_the_event_policies() {
+        let mut monitor = spec_monitor(PERIODIC, &["x"]);
+        assert!(matches!(monitor.tick(f64::NAN), Err(RtlolaError::InvalidTime(_))));
+
+        monitor.set_max_duration(Duration::from_secs(2));
+        assert!(matches!(monitor.tick(3.0), Err(RtlolaError::DurationExceeded { .. })));
+
+        let mut monitor = RtlolaMonitor::builder("", &["x"])
+            .spec_text(PERIODIC)
+            .duplicate_time(DuplicateTimePolicy::Reject)
+            .build()
+            .unwrap();
+        monitor.start().unwrap();
+        monitor.process_event(vec![make_float(1.0).unwrap()], Some(Duration::from_secs(1))).unwrap();
+        assert!(matches!(monitor.tick(1.0), Err(RtlolaError::DuplicateTimestamp(_))));
+    }
+
+    #[test]
+    fn pause_rejects_events_by_default() {
+        let mut monitor = ball_monitor();
+        monitor.pause();
+        assert!(monitor.is_paused());
+        assert!(matches!(monitor.process_event(ball_event(1.0, 0.0, 20.0), Some(Duration::from_secs(1))), Err(RtlolaError::Paused)));
+        assert_eq!(monitor.paused_events(), 0);
+        assert!(monitor.resume().unwrap().is_empty());
+        assert!(!monitor.is_paused());
+        let report = monitor.process_event(ball_event(1.0, 0.0, 20.0), Some(Duration::from_secs(2))).unwrap();
+        assert_eq!((report.seq, report.time), (Some(1), Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn resume_processes_the_buffered_events_in_order() {
+        let mut monitor = ball_monitor();
+        monitor.set_pause_policy(PausePolicy::Buffer);
+        monitor.pause();
+        for (i, velocity) in [1.0, -1.0].into_iter().enumerate() {
+            let result = monitor.process_event(ball_event(1.0, velocity, 20.0), Some(Duration::from_secs(i as u64 + 1)));
+            assert!(matches!(result, Err(RtlolaError::Paused)));
+        }
+        assert_eq!(monitor.paused_events(), 2);
+
+        let reports = monitor.resume().unwrap();
+        let times = reports.iter().map(|report| report.time).collect::<Vec<_>>();
+        assert_eq!(times, [1, 2].map(Duration::from_secs));
+        let fired = reports.iter().map(|report| report.fired_triggers().map(|(idx, _)| idx).collect::<Vec<_>>()).collect::<Vec<_>>();
+        assert_eq!(fired, [vec![1], vec![0]]);
+        assert_eq!(monitor.paused_events(), 0);
+    }
+
+    #[test]
+    fn resume_keeps_a_failing_event_buffered() {
+        let mut monitor = ball_monitor();
+        monitor.set_pause_policy(PausePolicy::Buffer);
+        monitor.pause();
+        for secs in [2, 1, 3] {
+            let result = monitor.process_event(ball_event(1.0, 0.0, 20.0), Some(Duration::from_secs(secs)));
+            assert!(matches!(result, Err(RtlolaError::Paused)));
+        }
+        // The event at 1s goes back in time and stays buffered with the one after it
+        assert!(matches!(monitor.resume(), Err(RtlolaError::TimeRegression { .. })));
+        assert_eq!(monitor.paused_events(), 2);
+        assert_eq!(monitor.last_report().unwrap().time, Duration::from_secs(2));
+
+        monitor.set_out_of_order_policy(OutOfOrderPolicy::Clamp);
+        let times = monitor.resume().unwrap().iter().map(|report| report.time).collect::<Vec<_>>();
+        assert_eq!(times, [2, 3].map(Duration::from_secs));
+        assert_eq!(monitor.paused_events(), 0);
+    }
+
+    #[test]
+    fn resume_submits_buffered_events_to_the_background_handler() {
+        let mut monitor = ball_monitor();
+        monitor.set_pause_policy(PausePolicy::Buffer);
+        monitor.pause();
+        for secs in [1, 2] {
+            let result = monitor.process_event(ball_event(1.0, 0.0, 20.0), Some(Duration::from_secs(secs)));
+            assert!(matches!(result, Err(RtlolaError::Paused)));
+        }
+        let (sender, receiver) = std::sync::mpsc::channel();
+        monitor.run_background(move |report| sender.send(report.time).unwrap()).unwrap();
+        assert!(monitor.resume().unwrap().is_empty());
+        assert_eq!(monitor.paused_events(), 0);
+        let times = receiver.iter().take(2).collect::<Vec<_>>();
+        assert_eq!(times, [1, 2].map(Duration::from_secs));
+        assert!(monitor.stop_background());
+    }
+
+    #[test]
+    fn tick_is_rejected_while_paused() {
+        let mut monitor = spec_monitor(PERIODIC, &["x"]);
+        monitor.set_pause_policy(PausePolicy::Buffer);
+        monitor.process_event(vec![make_float(1.0).unwrap()], Some(Duration::from_millis(500))).unwrap();
+        monitor.pause();
+        assert!(matches!(monitor.tick(2.5), Err(RtlolaError::Paused)));
+        // A tick is not an event and is not buffered
+        assert_eq!(monitor.paused_events(), 0);
+        monitor.resume().unwrap();
+        let times = monitor.tick(2.5).unwrap().iter().map(|report| report.time).collect::<Vec<_>>();
+        assert_eq!(times, [1, 2].map(Duration::from_secs));
+    }
+
+    #[test]
+    fn estimated_drain_needs_a_throughput() {
+        let mut monitor = ball_monitor();
+        assert_eq!(monitor.estimated_drain(), Some(Duration::ZERO));
+        monitor.submit_event(ball_event(1.0, 0.0, 20.0), Some(Duration::from_secs(1))).unwrap();
+        let start = Instant::now();
+        while monitor.pending_verdicts() == 0 && start.elapsed() < Duration::from_secs(5) {
+            thread::sleep(Duration::from_millis(1));
+        }
+        // Nothing was consumed yet
+        assert_eq!(monitor.estimated_drain(), None);
+    }
+
+    fn format_float(monitor: &RtlolaMonitor, x: f64) -> String {
+        monitor.format_number(make_float(x).unwrap(), RtlolaMonitor::DEFAULT_THRESHOLD)
+    }
+
+    #[test]
+    fn format_number_thresholds() {
+        let mut monitor = unstarted_ball_monitor();
+        assert_eq!(format_float(&monitor, 0.0), "0.0");
+        assert_eq!(format_float(&monitor, 1e-10), "0.0");
+        assert_eq!(format_float(&monitor, -1e-11), "0.0");
+        assert_eq!(format_float(&monitor, 2e-10), "2.000000e-10");
+        assert_eq!(format_float(&monitor, -5e-7), "-5.000000e-7");
+        assert_eq!(format_float(&monitor, 1e-6), "0.000001");
+        assert_eq!(format_float(&monitor, 12.5), "12.500000");
+
+        monitor.set_zero_epsilon(0.0);
+        assert_eq!(format_float(&monitor, 0.0), "0.0");
+        assert_eq!(format_float(&monitor, 1e-10), "1.000000e-10");
+    }
+
+    #[test]
+    fn format_number_handles_nan_and_infinity() {
+        let monitor = unstarted_ball_monitor();
+        // NaN never becomes a Value, so it cannot reach format_number
+        assert!(matches!(make_float(f64::NAN), Err(RtlolaError::NanFloat)));
+        assert_eq!(format_float(&monitor, f64::INFINITY), "inf");
+        assert_eq!(format_float(&monitor, f64::NEG_INFINITY), "-inf");
+    }
+
+    #[test]
+    fn precision_only_changes_the_digits() {
+        let mut monitor = unstarted_ball_monitor();
+        monitor.set_precision(2);
+        assert_eq!(format_float(&monitor, 1.5), "1.50");
+        assert_eq!(format_float(&monitor, 0.001), "0.00");
+        assert_eq!(format_float(&monitor, 5e-7), "5.00e-7");
+    }
+
+    fn nanos_monitor() -> RtlolaMonitor {
+        let mut monitor = ball_builder()
+            .timeout_ms(500)
+            .input_time(InputTime::RelativeNanos)
+            .build()
+            .unwrap();
+        monitor.start().unwrap();
+        monitor
+    }
+
+    #[test]
+    fn nanos_times_are_shown_exactly() {
+        let mut monitor = nanos_monitor();
+        let verdict = monitor.process_event_verdict(ball_event(1.0, 0.0, 20.0), Some(Duration::from_nanos(1_000_000_001))).unwrap();
+        assert!(verdict.starts_with("[1.000000001s] Processing new event"), "{}", verdict);
+
+        let mut monitor = ball_monitor();
+        let verdict = monitor.process_event_verdict(ball_event(1.0, 0.0, 20.0), Some(Duration::from_nanos(1_000_000_001))).unwrap();
+        assert!(verdict.starts_with("[1.000000s] Processing new event"), "{}", verdict);
+    }
+
+    #[test]
+    fn nanos_times_beyond_u64_are_rejected() {
+        let mut monitor = nanos_monitor();
+        let limit = Duration::from_nanos(u64::MAX);
+        let result = monitor.process_event(ball_event(1.0, 0.0, 20.0), Some(limit + Duration::from_nanos(1)));
+        assert!(matches!(result, Err(RtlolaError::DurationExceeded { limit: l, .. }) if l == limit));
+        assert!(monitor.process_event(ball_event(1.0, 0.0, 20.0), Some(limit)).is_ok());
+    }
+
+    #[test]
+    fn min_period_is_the_fastest_periodic_stream() {
+        let spec = "input x: Float64\noutput slow @1Hz := x.hold().defaults(to: 0.0)\noutput fast @10Hz := x.hold().defaults(to: 0.0)";
+        assert_eq!(spec_monitor(spec, &["x"]).min_period(), Some(Duration::from_millis(100)));
+        assert_eq!(ball_monitor().min_period(), None);
+    }
+
+    #[test]
+    fn backwards_times_under_each_out_of_order_policy() {
+        let mut monitor = ball_monitor();
+        monitor.process_event(ball_event(1.0, 0.0, 20.0), Some(Duration::from_secs(2))).unwrap();
+        let result = monitor.process_event(ball_event(1.0, 0.0, 20.0), Some(Duration::from_secs(1)));
+        assert!(matches!(result, Err(RtlolaError::TimeRegression { prev, got }) if prev == Duration::from_secs(2) && got == Duration::from_secs(1)));
+
+        let mut monitor = ball_builder().out_of_order(OutOfOrderPolicy::Clamp).build().unwrap();
+        monitor.start().unwrap();
+        monitor.process_event(ball_event(1.0, 0.0, 20.0), Some(Duration::from_secs(2))).unwrap();
+        let report = monitor.process_event(ball_event(1.0, 0.0, 20.0), Some(Duration::from_secs(1))).unwrap();
+        assert_eq!(report.time, Duration::from_secs(2));
+
+        let mut monitor = ball_builder().out_of_order(OutOfOrderPolicy::Allow).build().unwrap();
+        monitor.start().unwrap();
+        monitor.process_event(ball_event(1.0, 0.0, 20.0), Some(Duration::from_secs(2))).unwrap();
+        let report = monitor.process_event(ball_event(1.0, 0.0, 20.0), Some(Duration::from_secs(1))).unwrap();
+        assert_eq!(report.time, Duration::from_secs(1));
+    }
+
+    #[test]
+    fn input_schema_of_the_ball_spec() {
+        let monitor = unstarted_ball_monitor();
+        assert_eq!(monitor.input_schema(), "height:Float64,velocity:Float64,temperature:Float64");
+    }
+
+    // A Vec<u8> sink whose contents the test can still read after handing it to the monitor
+    #[derive(Clone, Default)]
+    struct SharedSink(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl io::Write for SharedSink {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn sink_receives_the_verdict_strings() {
+        let mut monitor = ball_monitor();
+        assert!(matches!(monitor.process_event_to_sink(ball_event(1.0, 0.0, 20.0), Some(Duration::from_secs(1))), Err(RtlolaError::NoSink)));
+
+        let sink = SharedSink::default();
+        monitor.set_sink(Box::new(sink.clone()));
+        monitor.process_event_to_sink(ball_event(1.0, -1.0, 20.0), Some(Duration::from_secs(2))).unwrap();
+        // Without a sink the event was not processed
+        let mut expected = ball_monitor();
+        let verdict = expected.process_event_verdict(ball_event(1.0, -1.0, 20.0), Some(Duration::from_secs(2))).unwrap();
+        assert_eq!(String::from_utf8(sink.0.lock().unwrap().clone()).unwrap(), verdict);
+        assert!(monitor.take_sink().is_some());
+    }
+
+    #[test]
+    fn quiet_unchanged_skips_verdicts_without_changes() {
+        let spec = "input a: Float64\ninput b: Float64\noutput o := a * 2.0";
+        let mut monitor = spec_monitor(spec, &["a", "b"]);
+        let only_b = || vec![Value::None, make_float(1.0).unwrap()];
+        assert!(!monitor.process_event_verdict(only_b(), Some(Duration::from_secs(1))).unwrap().is_empty());
+
+        monitor.set_quiet_unchanged(true);
+        assert_eq!(monitor.process_event_verdict(only_b(), Some(Duration::from_secs(2))).unwrap(), "");
+        let verdict = monitor.process_event_verdict(vec![make_float(1.0).unwrap(), Value::None], Some(Duration::from_secs(3))).unwrap();
+        assert!(verdict.contains("[Output][o]"), "{}", verdict);
+    }
+
+    #[test]
+    fn output_pacing_of_event_and_periodic_outputs() {
+        let monitor = ball_monitor();
+        assert_eq!(monitor.output_pacing("is_falling"), Some(Pacing::Event("velocity".to_string())));
+        assert_eq!(monitor.output_pacing("unknown"), None);
+
+        let spec = "input a: Float64\ninput b: Float64\noutput sum := a + b\noutput p @2Hz := a.hold().defaults(to: 0.0)";
+        let monitor = spec_monitor(spec, &["a", "b"]);
+        assert_eq!(monitor.output_pacing("sum"), Some(Pacing::Event("(a & b)".to_string())));
+        assert_eq!(monitor.output_pacing("p"), Some(Pacing::Periodic(Duration::from_millis(500))));
+    }
+
+    #[test]
+    fn plain_and_valued_trigger_lines() {
+        let spec = "input status: String\ntrigger status != \"ok\" \"Not ok\"\ntrigger eval when status != \"ok\" with status";
+        let mut monitor = RtlolaMonitor::builder("", &["status"]).spec_text(spec).color(ColorChoice::Never).build().unwrap();
+        monitor.start().unwrap();
+        let verdict = monitor.process_event_verdict(vec![Value::Str("timeout".into())], Some(Duration::from_secs(1))).unwrap();
+        // The constant message of a plain trigger is shown without its value, a valued trigger as message = value
+        assert!(verdict.contains("[Trigger] [#0] Not ok\n"), "{}", verdict);
+        assert!(verdict.contains("[Trigger] [#1] trigger_1 = timeout\n"), "{}", verdict);
+    }
+
+
+    #[test]
+    fn zero_timeout_waits_without_a_limit() {
+        let mut monitor = ball_builder().timeout_ms(0).build().unwrap();
+        assert_eq!(monitor.timeout, None);
+        monitor.start().unwrap();
+        let trace = (1..=50).map(|i| (ball_event(1.0, -1.0, 20.0), i as f64 * 0.1));
+        assert!(monitor.run_trace(trace).is_ok());
+        assert_eq!(monitor.finalize().events, 50);
+    }
+
+    #[test]
+    fn recv_verdict_times_out_only_with_a_limit() {
+        let (sender, receiver) = crossbeam_channel::unbounded::<Verdict>();
+        assert!(matches!(RtlolaMonitor::recv_verdict(&receiver, Some(Duration::from_millis(1))), Err(RtlolaError::Timeout)));
+        drop(sender);
+        assert!(matches!(RtlolaMonitor::recv_verdict(&receiver, None), Err(RtlolaError::Disconnected)));
+    }
+
+
+    #[test]
+    fn accept_event_errors_carry_the_detail() {
+        let mut monitor = ball_monitor();
+        // A mismatched value makes the interpreter's worker panic, so the next event cannot be sent to it
+        let mismatched = vec![Value::Bool(true), Value::Str("fast".into()), Value::Unsigned(1)];
+        assert!(monitor.process_event(mismatched, Some(Duration::from_secs(1))).is_err());
+        let err = monitor.process_event(ball_event(1.0, 0.0, 20.0), Some(Duration::from_secs(2))).unwrap_err();
+        let RtlolaError::AcceptEvent { event, time, reason } = &err else {
+            panic!("expected AcceptEvent, got {:?}", err);
+        };
+        assert_eq!((*event, *time), (1, Duration::from_secs(2)));
+        // Depending on how far the worker got, the interpreter reports the failed send or the worker's panic
+        assert!(reason.starts_with("Failed to send message") || reason.starts_with("Worker thread hung up"), "{}", reason);
+        assert_eq!(err.to_string(), format!("Failed to accept event 1 at 2s: {}", reason));
+    }
+
+
+    #[test]
+    fn never_seen_inputs_lists_the_inputs_without_values() {
+        let mut monitor = ball_monitor();
+        assert_eq!(monitor.never_seen_inputs(), BALL_INPUTS);
+        let partial = vec![make_float(1.0).unwrap(), Value::None, Value::None];
+        monitor.process_event(partial, Some(Duration::from_secs(1))).unwrap();
+        assert_eq!(monitor.never_seen_inputs(), ["velocity", "temperature"]);
+        let partial = vec![Value::None, Value::None, make_float(20.0).unwrap()];
+        monitor.process_event(partial, Some(Duration::from_secs(2))).unwrap();
+        assert_eq!(monitor.never_seen_inputs(), ["velocity"]);
+    }
+
+
+    #[test]
+    fn periodic_spec_in_offline_mode_is_flagged() {
+        let spec = "input x: Float64\noutput avg @1Hz := x.aggregate(over: 1s, using: avg).defaults(to: 0.0)";
+        let build = |mode, check| RtlolaMonitor::builder("", &["x"]).spec_text(spec).mode(mode).mode_check(check).build();
+        let err = build(MonitorMode::Offline, ModeCheck::Error).err().unwrap();
+        assert!(matches!(&err, RtlolaError::ModeIncompatible { reason } if reason.starts_with("every output is periodic")), "{:?}", err);
+        assert_eq!(err.code(), -22);
+        assert!(build(MonitorMode::Offline, ModeCheck::Warn).is_ok());
+        assert!(build(MonitorMode::Offline, ModeCheck::Off).is_ok());
+        assert!(build(MonitorMode::Online, ModeCheck::Error).is_ok());
+        // An event-based output is evaluated with the events in offline mode
+        let mixed = format!("{}\noutput y := x", spec);
+        assert!(RtlolaMonitor::builder("", &["x"]).spec_text(&mixed).mode_check(ModeCheck::Error).build().is_ok());
+    }
+
+
+    #[test]
+    fn last_report_matches_the_verdict_string() {
+        let mut monitor = ball_builder().color(ColorChoice::Never).build().unwrap();
+        monitor.start().unwrap();
+        let verdict = monitor.process_event_verdict(ball_event(0.1, -1.0, 40.0), Some(Duration::from_secs(1))).unwrap();
+        let report = monitor.last_report().unwrap().clone();
+        assert_eq!(report.time, Duration::from_secs(1));
+        for (idx, message) in report.fired_triggers() {
+            assert!(verdict.contains(&format!("[Trigger] [#{}] {}\n", idx, message)), "{}", verdict);
+        }
+        assert_eq!(report.fired_triggers().count(), verdict.matches("[Trigger]").count());
+        for output in &report.outputs {
+            assert!(verdict.contains(&format!("[Output][{}] [Value]", output.name)), "{}", verdict);
+        }
+        // A quiet event builds no report and keeps the previous one
+        monitor.process_event_quiet(ball_event(1.0, 0.0, 20.0), Some(Duration::from_secs(2))).unwrap();
+        assert_eq!(monitor.last_report(), Some(&report));
+    }
+
+
+    #[test]
+    fn evaluation_layers_follow_the_dependency_chain() {
+        let spec = "input a: Float64\ninput b: Float64\noutput c := a + b\noutput d := c * 2.0\noutput e := d + a\noutput f := b";
+        let monitor = RtlolaMonitor::builder("", &["a", "b"]).spec_text(spec).build().unwrap();
+        // e reads d, which reads c, so each is a layer above the stream it reads
+        assert_eq!(monitor.evaluation_layers(), [vec!["a", "b"], vec!["c", "f"], vec!["d"], vec!["e"]]);
+    }
+
+
+    #[test]
+    fn recorded_log_answers_trigger_and_output_queries() {
+        let mut monitor = ball_monitor();
+        monitor.set_record_log(true);
+        assert_eq!(monitor.history_len(), RtlolaMonitor::RECORD_LOG_LEN);
+        let events = [(1.0, -1.0, 20.0), (0.1, 1.0, 40.0), (0.5, -1.0, 30.0), (0.1, -1.0, 20.0), (2.0, 0.0, 25.0)];
+        for (secs, (height, velocity, temperature)) in (1..).zip(events) {
+            monitor.process_event(ball_event(height, velocity, temperature), Some(Duration::from_secs(secs))).unwrap();
+        }
+        // "Ball is falling" is trigger #0, "Ball is close to ground" #2
+        assert_eq!(monitor.triggers_in_range(0, 0.0, 10.0), [1.0, 3.0, 4.0]);
+        assert_eq!(monitor.triggers_in_range(0, 2.0, 3.5), [3.0]);
+        assert_eq!(monitor.triggers_in_range(2, 3.0, 5.0), [4.0]);
+        let fahrenheit = monitor.output_history("hotInFar").into_iter().map(|(time, value)| (time, value.to_string())).collect::<Vec<_>>();
+        assert_eq!(fahrenheit, [(1.0, "68".to_string()), (2.0, "104".to_string()), (3.0, "86".to_string()), (4.0, "68".to_string()), (5.0, "77".to_string())]);
+        assert!(monitor.output_history("missing").is_empty());
+
+        monitor.set_record_log(false);
+        assert!(monitor.triggers_in_range(0, 0.0, 10.0).is_empty());
+    }
+
+
+    #[test]
+    fn restart_recovers_from_a_failed_start() {
+        let mut monitor = unstarted_ball_monitor();
+        // Kill the interpreter's worker with a mismatched event sent behind the monitor's back,
+        // then wait until the interpreter notices, so the monitor's own start fails
+        monitor.monitor.start().unwrap();
+        monitor.monitor.accept_event(vec![Value::Bool(true), Value::Str("fast".into()), Value::Unsigned(1)], Duration::ZERO).unwrap();
+        while monitor.monitor.accept_event(ball_event(1.0, 0.0, 20.0), Duration::ZERO).is_ok() {
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert!(matches!(monitor.start(), Err(RtlolaError::Start(_))));
+        assert!(!monitor.is_started());
+
+        monitor.restart().unwrap();
+        assert!(monitor.is_started());
+        assert!(matches!(monitor.start(), Err(RtlolaError::Start(_))));
+        let report = monitor.process_event(ball_event(1.0, -1.0, 20.0), Some(Duration::from_secs(1))).unwrap();
+        assert_eq!(report.fired_triggers().count(), 1);
+        // A restart starts the run over
+        monitor.restart().unwrap();
+        assert_eq!(monitor.last_event_seq(), 0);
+        assert!(monitor.process_event(ball_event(1.0, -1.0, 20.0), Some(Duration::from_millis(500))).is_ok());
+    }
+
+
+    #[test]
+    fn input_transforms_apply_to_float_values() {
+        let spec = "input raw: Float64\ninput count: UInt64\noutput celsius := raw\noutput total := count";
+        let mut monitor = spec_monitor(spec, &["raw", "count"]);
+        monitor.set_input_transform("raw", 0.1, 2.0).unwrap();
+        monitor.set_input_transform("count", 0.1, 2.0).unwrap();
+        assert!(matches!(monitor.set_input_transform("missing", 1.0, 0.0), Err(RtlolaError::UnknownInput(_))));
+
+        let report = monitor.process_event(vec![make_float(100.0).unwrap(), Value::Unsigned(100)], Some(Duration::from_secs(1))).unwrap();
+        // The unsigned input ignores its transform
+        assert_eq!(report.inputs, [("raw".to_string(), make_float(12.0).unwrap()), ("count".to_string(), Value::Unsigned(100))]);
+        assert_eq!(monitor.current_output("celsius"), Some(make_float(12.0).unwrap()));
+
+        assert!(monitor.clear_input_transform("raw"));
+        assert!(!monitor.clear_input_transform("raw"));
+        let report = monitor.process_event(vec![make_float(100.0).unwrap(), Value::Unsigned(100)], Some(Duration::from_secs(2))).unwrap();
+        assert_eq!(report.inputs[0].1, make_float(100.0).unwrap());
+    }
+
+
+    #[test]
+    fn spec_source_round_trips_the_spec() {
+        let spec = "input x: Float64\n// doubled\noutput y := x * 2.0\n";
+        let monitor = RtlolaMonitor::builder("", &["x"]).spec_text(spec).build().unwrap();
+        assert_eq!(monitor.spec_source(), Some(spec));
+        // A monitor built from the returned source behaves like the original
+        let mut copy = RtlolaMonitor::builder("", &["x"]).spec_text(monitor.spec_source().unwrap()).build().unwrap();
+        copy.start().unwrap();
+        copy.process_event(vec![make_float(1.5).unwrap()], Some(Duration::from_secs(1))).unwrap();
+        assert_eq!(copy.current_output("y"), Some(make_float(3.0).unwrap()));
+
+        assert_eq!(ball_monitor().spec_source(), Some(fs::read_to_string(BALL_SPEC).unwrap().as_str()));
+        let from_mir = RtlolaMonitor::from_mir(monitor.ir().clone(), 500, &["x"]).unwrap();
+        assert_eq!(from_mir.spec_source(), None);
+    }
+
+
+    #[test]
+    fn unused_inputs_have_no_dependents() {
+        let spec = "input a: Float64\ninput typo: Float64\ninput b: Float64\ninput tick: Bool\ninput spare: Float64\n\
+                    output sum := a + b\noutput avg @1Hz := a.aggregate(over: 1s, using: avg).defaults(to: 0.0)\noutput ticked @tick := 1.0";
+        let inputs = ["a", "typo", "b", "tick", "spare"];
+        let monitor = RtlolaMonitor::builder("", &inputs).spec_text(spec).build().unwrap();
+        // tick is only used as the pacing of an output
+        assert_eq!(monitor.unused_inputs(), ["typo", "spare"]);
+        assert!(ball_monitor().unused_inputs().is_empty());
+    }
+
+
+    #[test]
+    fn rebased_times_start_at_the_first_event() {
+        let mut monitor = ball_builder().color(ColorChoice::Never).build().unwrap();
+        monitor.start().unwrap();
+        monitor.set_rebase_to_first_event(true);
+        let first = monitor.process_event_verdict(ball_event(1.0, 0.0, 20.0), Some(Duration::from_millis(2500))).unwrap();
+        assert!(first.starts_with("[0.000000s] Processing new event\n"), "{}", first);
+        let second = monitor.process_event_verdict(ball_event(1.0, 0.0, 20.0), Some(Duration::from_millis(3750))).unwrap();
+        assert!(second.starts_with("[1.250000s] Processing new event\n"), "{}", second);
+        // Reports keep the monitor's time
+        assert_eq!(monitor.last_report().unwrap().time, Duration::from_millis(3750));
+
+        monitor.set_rebase_to_first_event(false);
+        let third = monitor.process_event_verdict(ball_event(1.0, 0.0, 20.0), Some(Duration::from_secs(4))).unwrap();
+        assert!(third.starts_with("[4.000000s] Processing new event\n"), "{}", third);
+    }
+
+
+    #[test]
+    fn verdict_closures_run_per_event_in_registration_order() {
+        use std::sync::{Arc, Mutex};
+
+        let mut monitor = ball_monitor();
+        let counts = Arc::new(Mutex::new(BTreeMap::<usize, u64>::new()));
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let (counted, first) = (counts.clone(), order.clone());
+        monitor.on_verdict(move |report| {
+            for (idx, _) in report.fired_triggers() {
+                *counted.lock().unwrap().entry(idx).or_default() += 1;
+            }
+            first.lock().unwrap().push(("first", report.time));
+        });
+        let second = order.clone();
+        monitor.on_verdict(move |report| second.lock().unwrap().push(("second", report.time)));
+
+        monitor.process_event(ball_event(1.0, -1.0, 20.0), Some(Duration::from_secs(1))).unwrap();
+        monitor.process_event_verdict(ball_event(0.1, -1.0, 40.0), Some(Duration::from_secs(2))).unwrap();
+        monitor.process_event_quiet(ball_event(1.0, 1.0, 20.0), Some(Duration::from_secs(3))).unwrap();
+
+        // Falling twice, rising, close to the ground and hot once each
+        assert_eq!(*counts.lock().unwrap(), BTreeMap::from([(0, 2), (1, 1), (2, 1), (4, 1)]));
+        let expected = (1..=3).flat_map(|secs| [("first", Duration::from_secs(secs)), ("second", Duration::from_secs(secs))]).collect::<Vec<_>>();
+        assert_eq!(*order.lock().unwrap(), expected);
+
+        monitor.clear_verdict_callbacks();
+        monitor.process_event(ball_event(1.0, -1.0, 20.0), Some(Duration::from_secs(4))).unwrap();
+        assert_eq!(order.lock().unwrap().len(), 6);
+    }
+
+
+    #[test]
+    fn retries_wait_for_a_delayed_verdict() {
+        let mut monitor = RtlolaMonitor::new(BALL_SPEC, 100, &BALL_INPUTS).unwrap();
+        monitor.start().unwrap();
+        let verdict = monitor.process_event_raw(ball_event(1.0, -1.0, 20.0), Some(Duration::from_secs(1))).unwrap();
+        // Deliver the verdict after one timeout has passed
+        let delayed = |monitor: &mut RtlolaMonitor| {
+            let (sender, receiver) = crossbeam_channel::unbounded();
+            monitor.receiver = receiver;
+            let verdict = verdict.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(150));
+                sender.send(verdict).unwrap();
+            })
+        };
+
+        let sender = delayed(&mut monitor);
+        assert!(matches!(monitor.recv_next(), Err(RtlolaError::Timeout)));
+        sender.join().unwrap();
+
+        monitor.set_recv_retries(5);
+        let sender = delayed(&mut monitor);
+        assert_eq!(monitor.recv_next().unwrap().ts, Duration::from_secs(1));
+        sender.join().unwrap();
+    }
+
+
+    #[test]
+    fn changed_outputs_between_two_events() {
+        let spec = "input a: Float64\ninput b: Float64\noutput sum := a + b\noutput scaled := a * 2.0";
+        let mut monitor = spec_monitor(spec, &["a", "b"]);
+        let event = |a: f64, b: f64| vec![make_float(a).unwrap(), make_float(b).unwrap()];
+        let float = |x: f64| make_float(x).unwrap();
+
+        let first = monitor.process_event(event(1.0, 2.0), Some(Duration::from_secs(1))).unwrap();
+        // First values change from Value::None
+        assert_eq!(first.changed, [("sum".to_string(), Value::None, float(3.0)), ("scaled".to_string(), Value::None, float(2.0))]);
+
+        // sum stays 3.0, scaled changes
+        let second = monitor.process_event(event(2.0, 1.0), Some(Duration::from_secs(2))).unwrap();
+        let expected = [("scaled".to_string(), float(2.0), float(4.0))];
+        assert_eq!(monitor.changed_outputs(), expected);
+        assert_eq!(second.changed, expected);
+    }
+
+
+    #[test]
+    fn trigger_free_specs_fail_only_when_triggers_are_required() {
+        let spec = "input a: Float64\noutput doubled := a * 2.0";
+        let build = |require| RtlolaMonitor::builder("", &["a"]).spec_text(spec).require_triggers(require).build();
+        let err = build(true).err().unwrap();
+        assert!(matches!(err, RtlolaError::NoTriggers));
+        assert_eq!(err.code(), -24);
+        assert!(build(false).is_ok());
+        assert!(ball_builder().require_triggers(true).build().is_ok());
+    }
+
+
+    #[test]
+    fn timed_verdicts_show_their_output_changes() {
+        let spec = "input x: Float64\noutput total @1Hz := x.aggregate(over: 1s, using: sum)\noutput y := x";
+        let timed_verdict = |echo_timed_inputs| {
+            let mut monitor = RtlolaMonitor::builder("", &["x"]).spec_text(spec).color(ColorChoice::Never).build().unwrap();
+            monitor.start().unwrap();
+            monitor.set_echo_timed_inputs(echo_timed_inputs);
+            monitor.process_event_verdict(vec![make_float(2.5).unwrap()], Some(Duration::from_millis(500))).unwrap();
+            // The event at 1.5s passes the deadline at 1s, whose verdict comes first
+            monitor.process_event_verdict(vec![make_float(4.0).unwrap()], Some(Duration::from_millis(1500))).unwrap()
+        };
+
+        let timed = timed_verdict(false);
+        assert!(timed.contains("Deadline reached, 1 change\n"), "{}", timed);
+        assert!(timed.contains("[Output][total] [Value] =  2.500000\n"), "{}", timed);
+        assert!(!timed.contains("[Input]"), "{}", timed);
+        // With the echo, the timed verdict lists the latest inputs like an event verdict
+        let echoed = timed_verdict(true);
+        assert!(echoed.contains("[Input] [x] = 2.500000\n") && echoed.contains("[Output][total] [Value] =  2.500000\n"), "{}", echoed);
+    }
+
+
+    #[test]
+    fn current_output_holds_the_value_across_events() {
+        let spec = "input a: Float64\ninput flag: Bool\noutput held @flag := a.hold().defaults(to: 0.0)";
+        let mut monitor = spec_monitor(spec, &["a", "flag"]);
+        assert_eq!(monitor.current_output("held"), None);
+        monitor.process_event(vec![make_float(1.0).unwrap(), Value::Bool(true)], Some(Duration::from_secs(1))).unwrap();
+        for secs in [2, 3] {
+            monitor.process_event(vec![make_float(secs as f64).unwrap(), Value::None], Some(Duration::from_secs(secs))).unwrap();
+        }
+        // held was not evaluated by events 2 and 3, so it has not changed but still holds the value of event 1
+        assert!(monitor.changed_outputs().is_empty());
+        assert_eq!(monitor.current_output("held"), Some(make_float(1.0).unwrap()));
+        assert_eq!(monitor.current_output("missing"), None);
+    }
+
+
+    #[test]
+    fn new_owned_takes_names_built_at_runtime() {
+        let names = BALL_INPUTS.map(String::from).to_vec();
+        let mut monitor = RtlolaMonitor::new_owned(BALL_SPEC, 500, names.clone()).unwrap();
+        assert_eq!(monitor.input_names(), names);
+        monitor.start().unwrap();
+        let report = monitor.process_event(ball_event(1.0, -1.0, 20.0), Some(Duration::from_secs(1))).unwrap();
+        assert_eq!(report.fired_triggers().map(|(idx, _)| idx).collect::<Vec<_>>(), [0]);
+    }
+
+
+    #[test]
+    fn two_events_at_the_same_time_under_each_policy() {
+        for policy in [DuplicateTimePolicy::Allow, DuplicateTimePolicy::Warn, DuplicateTimePolicy::Reject] {
+            let mut monitor = ball_builder().duplicate_time(policy).build().unwrap();
+            monitor.start().unwrap();
+            monitor.process_event(ball_event(1.0, 0.0, 20.0), Some(Duration::from_secs(1))).unwrap();
+            let second = monitor.process_event(ball_event(1.0, 0.0, 20.0), Some(Duration::from_secs(1)));
+            match policy {
+                DuplicateTimePolicy::Allow | DuplicateTimePolicy::Warn => assert!(second.is_ok(), "{:?}", policy),
+                DuplicateTimePolicy::Reject => {
+                    assert!(matches!(second, Err(RtlolaError::DuplicateTimestamp(time)) if time == Duration::from_secs(1)));
+                    // The rejected event was not processed, a later one is
+                    assert_eq!(monitor.last_event_seq(), 1);
+                    assert!(monitor.process_event(ball_event(1.0, 0.0, 20.0), Some(Duration::from_secs(2))).is_ok());
+                },
+            }
+        }
+    }
+
+
+    #[test]
+    fn triggers_split_into_dormant_and_fired() {
+        let mut monitor = ball_monitor();
+        assert_eq!(monitor.dormant_triggers(), [0, 1, 2, 3, 4]);
+        assert!(monitor.fired_triggers().is_empty());
+        // Falling and close to the ground, then hot
+        monitor.process_event(ball_event(0.1, -1.0, 20.0), Some(Duration::from_secs(1))).unwrap();
+        monitor.process_event(ball_event(1.0, 0.0, 40.0), Some(Duration::from_secs(2))).unwrap();
+        assert_eq!(monitor.fired_triggers(), [0, 2, 4]);
+        assert_eq!(monitor.dormant_triggers(), [1, 3]);
+    }
+
+
+    #[test]
+    fn input_mapping_routes_names_to_the_given_inputs() {
+        let mapping = vec![("temp".to_string(), 2), ("h".to_string(), 0), ("v".to_string(), 1)];
+        let mut monitor = RtlolaMonitor::with_input_mapping(BALL_SPEC, 500, mapping).unwrap();
+        monitor.start().unwrap();
+        assert_eq!(monitor.input_names(), ["temp", "h", "v"]);
+        // In mapping order: temperature 40, height 0.1, velocity -1
+        let report = monitor.process_event(ball_event(40.0, 0.1, -1.0), Some(Duration::from_secs(1))).unwrap();
+        assert_eq!(report.fired_triggers().map(|(idx, _)| idx).collect::<Vec<_>>(), [0, 2, 4]);
+        assert_eq!(monitor.current_output("hotInFar"), Some(make_float(104.0).unwrap()));
+
+        let out_of_range = vec![("h".to_string(), 0), ("extra".to_string(), 3)];
+        let err = RtlolaMonitor::with_input_mapping(BALL_SPEC, 500, out_of_range).err().unwrap();
+        assert!(matches!(&err, RtlolaError::InputReference { name, reference: 3, inputs: 3 } if name == "extra"), "{:?}", err);
+    }
+
+
+    #[test]
+    fn flush_on_drop_writes_unconsumed_verdicts_to_the_sink() {
+        let spec = "input x: Float64\noutput total @1Hz := x.aggregate(over: 1s, using: sum)\noutput y := x";
+        let dropped_output = |flush| {
+            let mut monitor = RtlolaMonitor::builder("", &["x"]).spec_text(spec).color(ColorChoice::Never).build().unwrap();
+            monitor.start().unwrap();
+            monitor.set_flush_on_drop(flush);
+            let sink = SharedSink::default();
+            monitor.set_sink(Box::new(sink.clone()));
+            monitor.process_event(vec![make_float(2.5).unwrap()], Some(Duration::from_millis(500))).unwrap();
+            // Returns the timed verdict at 1s and leaves the event's own verdict queued
+            monitor.process_event(vec![make_float(4.0).unwrap()], Some(Duration::from_millis(1500))).unwrap();
+            drop(monitor);
+            String::from_utf8(sink.0.lock().unwrap().clone()).unwrap()
+        };
+
+        let flushed = dropped_output(true);
+        assert!(flushed.contains("[1.500000s] Processing new event\n"), "{}", flushed);
+        assert!(flushed.contains("[Output][y] [Value] =  4.000000\n"), "{}", flushed);
+        assert_eq!(dropped_output(false), "");
+    }
+
+
+    #[test]
+    fn outputs_matching_selects_by_glob() {
+        let spec = "input a: Float64\ninput b: Float64\noutput car_1_speed := a\noutput car_2_speed := b\noutput car_12_speed := a + b\n\
+                    output car_1_fuel := a\noutput truck_1_speed := b\ntrigger a > b \"faster\"";
+        let monitor = RtlolaMonitor::builder("", &["a", "b"]).spec_text(spec).build().unwrap();
+        assert_eq!(monitor.outputs_matching("car_*_speed"), ["car_1_speed", "car_2_speed", "car_12_speed"]);
+        assert_eq!(monitor.outputs_matching("car_?_speed"), ["car_1_speed", "car_2_speed"]);
+        assert_eq!(monitor.outputs_matching("*_1_*"), ["car_1_speed", "car_1_fuel", "truck_1_speed"]);
+        assert_eq!(monitor.outputs_matching("trigger_*"), ["trigger_0"]);
+        assert!(monitor.outputs_matching("bus_*").is_empty());
+    }
+
+    #[test]
+    fn glob_wildcards() {
+        let matches = |pattern: &str, name: &str| glob_match(&pattern.chars().collect::<Vec<_>>(), &name.chars().collect::<Vec<_>>());
+        assert!(matches("*", "") && matches("**", "abc") && matches("a*c", "abbbc") && matches("a*b*c", "abxbyc"));
+        assert!(!matches("?", "") && !matches("a*c", "abcd") && !matches("abc", "ab"));
+    }
+
+
+    #[test]
+    fn falling_edge_reports_is_safe_only_when_it_turns_false() {
+        let spec = "input distance: Float64\noutput is_safe := distance > 10.0";
+        let mut monitor = spec_monitor(spec, &["distance"]);
+        monitor.set_output_edge_mode("is_safe", EdgeMode::FallingEdge).unwrap();
+        assert!(matches!(monitor.set_output_edge_mode("missing", EdgeMode::RisingEdge), Err(RtlolaError::UnknownOutput(_))));
+
+        let reported = [20.0, 15.0, 5.0, 3.0, 12.0, 4.0].into_iter().zip(1..)
+            .map(|(distance, secs)| {
+                let report = monitor.process_event(vec![make_float(distance).unwrap()], Some(Duration::from_secs(secs))).unwrap();
+                report.outputs.iter().any(|output| output.name == "is_safe")
+            })
+            .collect::<Vec<_>>();
+        // Only the changes from true to false at 5.0 and 4.0 are reported
+        assert_eq!(reported, [false, false, true, false, false, true]);
+        // current_output still sees every value
+        assert_eq!(monitor.current_output("is_safe"), Some(Value::Bool(false)));
+
+        monitor.set_output_edge_mode("is_safe", EdgeMode::Level).unwrap();
+        let report = monitor.process_event(vec![make_float(4.0).unwrap()], Some(Duration::from_secs(7))).unwrap();
+        assert_eq!(report.outputs.len(), 1);
+    }
+}
@@ -0,0 +1,98 @@
+use std::sync::mpsc::{Receiver, Sender};
+use rtlola_interpreter::Value;
+use crate::error::RtlolaError;
+use crate::rtlola_monitor::{event_time, RtlolaMonitor};
+use crate::verdict::VerdictReport;
+
+// A stream of events for RtlolaMonitor::run_source, e.g. a file, a socket or a database cursor.
+// Each event holds one value per input name in the monitor's input order and its time in seconds,
+// which must be finite and non-negative, otherwise that event fails with RtlolaError::InvalidTime.
+// Value::None leaves an input without a new value.
+pub trait EventSource {
+    // The next event, None once the source is exhausted.
+    // An Err is reported for that event and the source is asked for the next one.
+    fn next_event(&mut self) -> Option<Result<(Vec<Value>, f64), RtlolaError>>;
+}
+
+// Events sent by a producer thread. Waits for the next event and is exhausted once every sender is dropped.
+impl EventSource for Receiver<(Vec<Value>, f64)> {
+    fn next_event(&mut self) -> Option<Result<(Vec<Value>, f64), RtlolaError>> {
+        self.recv().ok().map(Ok)
+    }
+}
+
+impl RtlolaMonitor {
+    // Process every event of a source, yielding one result per event until the source is exhausted
+    pub fn run_source<S: EventSource>(&mut self, mut src: S) -> impl Iterator<Item = Result<VerdictReport, RtlolaError>> {
+        std::iter::from_fn(move || {
+            let event = src.next_event()?;
+            Some(event.and_then(|(inputs, time)| self.process_event(inputs, Some(event_time(time)?))))
+        })
+    }
+
+    // Process the events a producer sends on `rx` and send the report of each verdict on `out`, blocking the
+    // calling thread while waiting for events. When every sender of `rx` is dropped, the reports of verdicts
+    // that are still queued, e.g. timed verdicts of the last event, are sent as well and the number of processed
+    // events is returned. Stops at the first event that fails, and with Disconnected once `out` has no receiver.
+    pub fn run_from_channel(&mut self, rx: Receiver<(Vec<Value>, f64)>, out: Sender<VerdictReport>) -> Result<u64, RtlolaError> {
+        let mut processed = 0;
+        for report in self.run_source(rx) {
+            out.send(report?).map_err(|_| RtlolaError::Disconnected)?;
+            processed += 1;
+        }
+        for report in self.queued_reports() {
+            out.send(report).map_err(|_| RtlolaError::Disconnected)?;
+        }
+        Ok(processed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+    use std::time::Duration;
+    use super::*;
+    use crate::test_util::{ball_event, ball_monitor};
+
+    #[test]
+    fn invalid_times_fail_only_their_event() {
+        let mut monitor = ball_monitor();
+        let (tx, rx) = mpsc::channel();
+                for time in [1.0, f64::NAN, 2.0] {
+            tx.send((ball_event(1.0, 0.0, 20.0), time)).unwrap();
+        }
+        drop(tx);
+        let results = monitor.run_source(rx).collect::<Vec<_>>();
+        assert_eq!(results.len(), 3);
+        assert!(matches!(results[1], Err(RtlolaError::InvalidTime(_))));
+        assert_eq!(results[2].as_ref().unwrap().time, Duration::from_secs(2));
+    }
+
+
+    #[test]
+    fn run_from_channel_between_producer_and_consumer() {
+        let mut monitor = ball_monitor();
+        let (tx, rx) = mpsc::channel();
+        let (out, reports) = mpsc::channel();
+        let producer = std::thread::spawn(move || {
+            for i in 1..=5 {
+                tx.send((ball_event(i as f64, -1.0, 20.0), i as f64)).unwrap();
+            }
+        });
+        let consumer = std::thread::spawn(move || reports.iter().map(|report: VerdictReport| report.time).collect::<Vec<_>>());
+        // Returns once the producer is done and dropped its sender
+        assert_eq!(monitor.run_from_channel(rx, out).unwrap(), 5);
+        producer.join().unwrap();
+        assert_eq!(consumer.join().unwrap(), (1..=5).map(Duration::from_secs).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn run_from_channel_stops_without_a_consumer() {
+        let mut monitor = ball_monitor();
+        let (tx, rx) = mpsc::channel();
+        let (out, reports) = mpsc::channel();
+        drop(reports);
+        tx.send((ball_event(1.0, 0.0, 20.0), 1.0)).unwrap();
+        assert!(matches!(monitor.run_from_channel(rx, out), Err(RtlolaError::Disconnected)));
+    }
+}
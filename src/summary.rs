@@ -0,0 +1,308 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use rtlola_interpreter::{
+    monitor::Change,
+    queued::VerdictKind,
+    rtlola_mir::{OutputKind, PacingType, RtLolaMir},
+    Value,
+};
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use crate::backend::Verdict;
+
+// Aggregate results of a run, returned by RtlolaMonitor::finalize
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct RunSummary {
+    pub events: u64, // Events accepted by the monitor
+    pub duration: Duration, // Time of the last verdict
+    pub trigger_counts: BTreeMap<usize, u64>, // How often each trigger fired, by trigger index
+    pub outputs: BTreeMap<String, OutputSummary>,
+}
+
+// Values of a named output over the run. All instances of a parameterized output are combined.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct OutputSummary {
+    pub updates: u64,
+    // Only tracked for numbers and strings, which have an order
+    pub min: Option<Value>,
+    pub max: Option<Value>,
+    pub last: Value,
+}
+
+// Aggregate of the verdicts in one window [start, end) of event time, returned by RtlolaMonitor::windowed_summary
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct WindowSummary {
+    pub start: Duration,
+    pub end: Duration,
+    pub events: u64, // Verdicts of events in the window
+    pub trigger_counts: BTreeMap<usize, u64>,
+    pub outputs: BTreeMap<String, OutputSummary>,
+}
+
+impl OutputSummary {
+    fn new(value: Value) -> Self {
+        let ordered = Self::ordered(&value);
+        Self {
+            updates: 1,
+            min: ordered.then(|| value.clone()),
+            max: ordered.then(|| value.clone()),
+            last: value,
+        }
+    }
+
+    fn update(&mut self, value: Value) {
+        self.updates += 1;
+        if Self::ordered(&value) {
+            // Value::cmp panics on mismatched variants, so only compare values of the same kind
+            let same_kind = |other: &Value| std::mem::discriminant(other) == std::mem::discriminant(&value);
+            if self.min.as_ref().is_none_or(|min| same_kind(min) && value < *min) {
+                self.min = Some(value.clone());
+            }
+            if self.max.as_ref().is_none_or(|max| same_kind(max) && value > *max) {
+                self.max = Some(value.clone());
+            }
+        }
+        self.last = value;
+    }
+
+    fn ordered(value: &Value) -> bool {
+        matches!(value, Value::Unsigned(_) | Value::Signed(_) | Value::Float(_) | Value::Str(_))
+    }
+}
+
+// Run statistics shared between the monitor and its background consumer
+#[derive(Debug, Clone)]
+pub(crate) struct RunStats(Arc<Mutex<Stats>>);
+
+#[derive(Debug, Default)]
+struct Stats {
+    summary: RunSummary,
+    consumed: u64, // Verdicts consumed so far
+    first_consumed: Option<Instant>,
+    last_consumed: Option<Instant>,
+    instances: HashMap<(usize, Vec<Value>), Value>, // Alive instances of parameterized outputs and their latest value
+    window: Option<Window>,
+    triggers: Vec<TriggerState>,
+}
+
+#[derive(Debug)]
+struct TriggerState {
+    out_idx: usize,
+    index: usize,
+    timed: bool, // Evaluated by timed verdicts instead of event verdicts
+    active: bool, // Fired the last time a verdict of its kind was recorded
+}
+
+// Accumulation for windowed_summary
+#[derive(Debug)]
+struct Window {
+    len: Duration,
+    current: Option<WindowSummary>, // None until the first verdict after the window was configured
+    completed: Option<WindowSummary>, // The latest window that ended and was not taken yet
+}
+
+impl Window {
+    // Close the current window if ts is past its end and start the window containing ts
+    fn advance(&mut self, ts: Duration, trigger_counts: &BTreeMap<usize, u64>) -> &mut WindowSummary {
+        if self.current.as_ref().is_some_and(|current| ts >= current.end) {
+            self.completed = self.current.take();
+        }
+        let len = self.len;
+        self.current.get_or_insert_with(|| {
+            let start = Duration::from_nanos((ts.as_nanos() / len.as_nanos() * len.as_nanos()) as u64);
+            WindowSummary {
+                start,
+                end: start + len,
+                trigger_counts: trigger_counts.keys().map(|idx| (*idx, 0)).collect(),
+                ..WindowSummary::default()
+            }
+        })
+    }
+}
+
+fn add_value(outputs: &mut BTreeMap<String, OutputSummary>, name: &str, value: &Value) {
+    match outputs.get_mut(name) {
+        Some(output) => output.update(value.clone()),
+        None => {
+            outputs.insert(name.to_string(), OutputSummary::new(value.clone()));
+        },
+    }
+}
+
+impl RunStats {
+    pub(crate) fn new(ir: &RtLolaMir) -> Self {
+        let trigger_counts = ir.outputs.iter()
+            .filter_map(|output| match output.kind {
+                OutputKind::Trigger(idx) => Some((idx, 0)),
+                OutputKind::NamedOutput(_) => None,
+            })
+            .collect();
+        let summary = RunSummary { trigger_counts, ..RunSummary::default() };
+        let triggers = ir.outputs.iter()
+            .enumerate()
+            .filter_map(|(out_idx, output)| match output.kind {
+                OutputKind::Trigger(index) => Some(TriggerState {
+                    out_idx,
+                    index,
+                    timed: matches!(output.eval.eval_pacing, PacingType::GlobalPeriodic(_) | PacingType::LocalPeriodic(_)),
+                    active: false,
+                }),
+                OutputKind::NamedOutput(_) => None,
+            })
+            .collect();
+        Self(Arc::new(Mutex::new(Stats { summary, triggers, ..Stats::default() })))
+    }
+
+    pub(crate) fn events(&self) -> u64 {
+        self.0.lock().unwrap().summary.events
+    }
+
+    pub(crate) fn record_event(&self) {
+        self.0.lock().unwrap().summary.events += 1;
+    }
+
+    pub(crate) fn record(&self, verdict: &Verdict, ir: &RtLolaMir) {
+        let mut stats = self.0.lock().unwrap();
+        let now = Instant::now();
+        stats.consumed += 1;
+        stats.first_consumed.get_or_insert(now);
+        stats.last_consumed = Some(now);
+
+        stats.summary.duration = stats.summary.duration.max(verdict.ts);
+        let Stats { summary, instances, window, triggers, .. } = &mut *stats;
+        let timed = verdict.kind == VerdictKind::Timed;
+        for trigger in triggers.iter_mut().filter(|trigger| trigger.timed == timed) {
+            trigger.active = verdict.verdict.outputs.iter()
+                .filter(|(out_idx, _)| *out_idx == trigger.out_idx)
+                .flat_map(|(_, changes)| changes)
+                .any(|change| matches!(change, Change::Value(_, value) if *value != Value::Bool(false)));
+        }
+        let mut window = window.as_mut().map(|window| window.advance(verdict.ts, &summary.trigger_counts));
+        if let (Some(window), VerdictKind::Event) = (&mut window, verdict.kind) {
+            window.events += 1;
+        }
+
+        for (out_idx, changes) in &verdict.verdict.outputs {
+            for change in changes {
+                let value = match change {
+                    Change::Spawn(params) => {
+                        instances.entry((*out_idx, params.clone())).or_insert(Value::None);
+                        continue;
+                    },
+                    Change::Close(params) => {
+                        instances.remove(&(*out_idx, params.clone()));
+                        continue;
+                    },
+                    Change::Value(params, value) => {
+                        if let Some(params) = params {
+                            instances.insert((*out_idx, params.clone()), value.clone());
+                        }
+                        value
+                    },
+                };
+                match &ir.outputs[*out_idx].kind {
+                    OutputKind::NamedOutput(name) => {
+                        add_value(&mut summary.outputs, name, value);
+                        if let Some(window) = &mut window {
+                            add_value(&mut window.outputs, name, value);
+                        }
+                    },
+                    OutputKind::Trigger(idx) => {
+                        *summary.trigger_counts.entry(*idx).or_default() += 1;
+                        if let Some(window) = &mut window {
+                            *window.trigger_counts.entry(*idx).or_default() += 1;
+                        }
+                    },
+                }
+            }
+        }
+    }
+
+    // Parameters and latest value of the alive instances of an output, Value::None before the first value
+    pub(crate) fn instances(&self, out_idx: usize) -> Vec<(Vec<Value>, Value)> {
+        self.0.lock().unwrap().instances.iter()
+            .filter(|((idx, _), _)| *idx == out_idx)
+            .map(|((_, params), value)| (params.clone(), value.clone()))
+            .collect()
+    }
+
+    // Take the latest completed window of length len. Switching to a different len discards the accumulation.
+    pub(crate) fn take_window(&self, len: Duration) -> Option<WindowSummary> {
+        if len.is_zero() {
+            return None;
+        }
+        let mut stats = self.0.lock().unwrap();
+        match &mut stats.window {
+            Some(window) if window.len == len => window.completed.take(),
+            window => {
+                *window = Some(Window { len, current: None, completed: None });
+                None
+            },
+        }
+    }
+
+    // None if there is no trigger with this index
+    pub(crate) fn trigger_active(&self, index: usize) -> Option<bool> {
+        let stats = self.0.lock().unwrap();
+        stats.triggers.iter().find(|trigger| trigger.index == index).map(|trigger| trigger.active)
+    }
+
+    pub(crate) fn last_value(&self, name: &str) -> Option<Value> {
+        self.0.lock().unwrap().summary.outputs.get(name).map(|output| output.last.clone())
+    }
+
+    // Verdicts consumed per second, averaged from the first to the latest consumed verdict.
+    // None until two verdicts were consumed at distinct instants.
+    pub(crate) fn throughput(&self) -> Option<f64> {
+        let stats = self.0.lock().unwrap();
+        let span = stats.last_consumed?.duration_since(stats.first_consumed?).as_secs_f64();
+        (stats.consumed > 1 && span > 0.0).then(|| (stats.consumed - 1) as f64 / span)
+    }
+
+    pub(crate) fn trigger_counts(&self) -> BTreeMap<usize, u64> {
+        self.0.lock().unwrap().summary.trigger_counts.clone()
+    }
+
+    pub(crate) fn snapshot(&self) -> RunSummary {
+        self.0.lock().unwrap().summary.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rtlola_monitor::make_float;
+    use crate::test_util::{ball_event, ball_monitor};
+
+    #[test]
+    fn windowed_summary_over_two_windows() {
+        let mut monitor = ball_monitor();
+        let window = Duration::from_secs(60);
+        assert_eq!(monitor.windowed_summary(window), None);
+
+        let mut summaries = Vec::new();
+        let events = [(1.0, -1.0, 20.0, 10), (4.2, 1.0, 40.0, 50), (0.1, -1.0, 25.0, 70), (2.0, 0.0, 20.0, 130)];
+        for (height, velocity, temperature, secs) in events {
+            monitor.process_event(ball_event(height, velocity, temperature), Some(Duration::from_secs(secs))).unwrap();
+            summaries.extend(monitor.windowed_summary(window));
+        }
+
+        let [first, second] = summaries.as_slice() else {
+            panic!("expected two windows, got {:?}", summaries);
+        };
+        assert_eq!((first.start, first.end, first.events), (Duration::ZERO, window, 2));
+        // falling, rising and hot fired once each
+        assert_eq!(first.trigger_counts, BTreeMap::from([(0, 1), (1, 1), (2, 0), (3, 0), (4, 1)]));
+        let fahrenheit = &first.outputs["hotInFar"];
+        assert_eq!((fahrenheit.updates, fahrenheit.min.clone(), fahrenheit.max.clone()), (2, Some(make_float(68.0).unwrap()), Some(make_float(104.0).unwrap())));
+
+        assert_eq!((second.start, second.end, second.events), (window, 2 * window, 1));
+        // The counts of the second window start from zero, falling and close to the ground fired
+        assert_eq!(second.trigger_counts, BTreeMap::from([(0, 1), (1, 0), (2, 1), (3, 0), (4, 0)]));
+        assert_eq!(second.outputs["hotInFar"].last, make_float(77.0).unwrap());
+    }
+}
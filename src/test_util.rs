@@ -0,0 +1,33 @@
+// Monitors and events shared by the unit tests, mostly for the bouncing ball spec in src/ball_spec.lola
+use rtlola_interpreter::Value;
+use crate::builder::RtlolaMonitorBuilder;
+use crate::rtlola_monitor::{make_float, RtlolaMonitor};
+
+pub(crate) const BALL_SPEC: &str = "src/ball_spec.lola";
+pub(crate) const BALL_INPUTS: [&str; 3] = ["height", "velocity", "temperature"];
+
+// For ball monitors that need settings
+pub(crate) fn ball_builder() -> RtlolaMonitorBuilder {
+    RtlolaMonitor::builder(BALL_SPEC, &BALL_INPUTS)
+}
+
+pub(crate) fn unstarted_ball_monitor() -> RtlolaMonitor {
+    RtlolaMonitor::new(BALL_SPEC, 500, &BALL_INPUTS).unwrap()
+}
+
+pub(crate) fn ball_monitor() -> RtlolaMonitor {
+    let mut monitor = unstarted_ball_monitor();
+    monitor.start().unwrap();
+    monitor
+}
+
+pub(crate) fn ball_event(height: f64, velocity: f64, temperature: f64) -> Vec<Value> {
+    [height, velocity, temperature].into_iter().map(|x| make_float(x).unwrap()).collect()
+}
+
+// A started monitor of an inline spec
+pub(crate) fn spec_monitor(spec: &str, inputs: &[&str]) -> RtlolaMonitor {
+    let mut monitor = RtlolaMonitor::builder("", inputs).spec_text(spec).timeout_ms(500).build().unwrap();
+    monitor.start().unwrap();
+    monitor
+}
@@ -0,0 +1,117 @@
+use rtlola_interpreter::{monitor::Change, Value};
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use crate::rtlola_monitor::RtlolaMonitor;
+
+// The recorded run as one document for charting: a series per input and named output and the points at which
+// triggers fired. Built from the verdict history, so it covers the verdicts kept by set_record_log or set_history_len.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Timeline {
+    pub inputs: Vec<Series>,
+    pub outputs: Vec<Series>,
+    pub triggers: Vec<TriggerPoint>,
+}
+
+// The values of a stream as (time in seconds, value), oldest first.
+// A parameterized output has one point per instance that produced a value.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Series {
+    pub name: String,
+    pub points: Vec<(f64, Value)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct TriggerPoint {
+    pub index: usize,
+    pub time: f64, // Seconds
+    pub message: String,
+}
+
+impl RtlolaMonitor {
+    // The recorded history as a Timeline, with inputs and outputs in spec order
+    pub fn timeline(&self) -> Timeline {
+        let mut inputs = self.ir().inputs.iter()
+            .map(|input| Series { name: input.name.clone(), points: Vec::new() })
+            .collect::<Vec<_>>();
+        let mut outputs = self.outputs().into_iter()
+            .map(|name| Series { name, points: Vec::new() })
+            .collect::<Vec<_>>();
+        let mut triggers = Vec::new();
+        for report in self.history() {
+            let time = report.time.as_secs_f64();
+            for (name, value) in &report.inputs {
+                if let Some(series) = inputs.iter_mut().find(|series| &series.name == name) {
+                    series.points.push((time, value.clone()));
+                }
+            }
+            for output in &report.outputs {
+                if let Change::Value(_, value) = &output.change
+                    && let Some(series) = outputs.iter_mut().find(|series| series.name == output.name)
+                {
+                    series.points.push((time, value.clone()));
+                }
+            }
+            triggers.extend(report.fired_triggers().map(|(index, message)| TriggerPoint {
+                index,
+                time,
+                message: message.to_string(),
+            }));
+        }
+        Timeline { inputs, outputs, triggers }
+    }
+
+    // The timeline as a JSON object {"inputs": [...], "outputs": [...], "triggers": [...]},
+    // each series as {"name": ..., "points": [[time, value], ...]}
+    #[cfg(feature = "serde")]
+    pub fn timeline_json(&self) -> String {
+        serde_json::to_string(&self.timeline()).expect("timeline is always serializable")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use super::*;
+    use crate::rtlola_monitor::make_float;
+    use crate::test_util::{ball_event, ball_monitor, BALL_INPUTS};
+
+    fn recorded_run() -> RtlolaMonitor {
+        let mut monitor = ball_monitor();
+        monitor.set_record_log(true);
+        for (i, (height, velocity)) in [(1.0, 0.0), (0.5, -1.0)].into_iter().enumerate() {
+            monitor.process_event(ball_event(height, velocity, 20.0), Some(Duration::from_secs(i as u64 + 1))).unwrap();
+        }
+        monitor
+    }
+
+    #[test]
+    fn timeline_has_a_series_per_stream_and_the_fired_triggers() {
+        let timeline = recorded_run().timeline();
+        let names = timeline.inputs.iter().map(|series| series.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, BALL_INPUTS);
+        assert_eq!(timeline.inputs[0].points, [(1.0, make_float(1.0).unwrap()), (2.0, make_float(0.5).unwrap())]);
+        let falling = timeline.outputs.iter().find(|series| series.name == "is_falling").unwrap();
+        assert_eq!(falling.points, [(1.0, Value::Bool(false)), (2.0, Value::Bool(true))]);
+        assert_eq!(timeline.triggers, [TriggerPoint { index: 0, time: 2.0, message: "Ball is falling".to_string() }]);
+    }
+
+    #[test]
+    fn timeline_is_empty_without_recording() {
+        let mut monitor = ball_monitor();
+        monitor.process_event(ball_event(1.0, -1.0, 20.0), Some(Duration::from_secs(1))).unwrap();
+        let timeline = monitor.timeline();
+        assert!(timeline.inputs.iter().chain(&timeline.outputs).all(|series| series.points.is_empty()));
+        assert!(timeline.triggers.is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn timeline_json_has_points_as_pairs() {
+        let json: serde_json::Value = serde_json::from_str(&recorded_run().timeline_json()).unwrap();
+        assert_eq!(json["inputs"][0]["points"][1], serde_json::json!([2.0, {"Float": 0.5}]));
+        assert_eq!(json["triggers"][0], serde_json::json!({"index": 0, "time": 2.0, "message": "Ball is falling"}));
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use rtlola_interpreter::{
+    monitor::{Change, TotalIncremental},
+    queued::{QueuedVerdict, VerdictKind},
+    rtlola_mir::{OutputKind, RtLolaMir},
+    time::RelativeFloat,
+    Value,
+};
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+// A change of a named output stream
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct OutputChange {
+    pub name: String,
+    pub change: Change,
+}
+
+// A change of a trigger, identified by its trigger index
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct TriggerChange {
+    pub index: usize,
+    pub change: Change,
+}
+
+// Structured form of a single verdict, independent of any string formatting
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct VerdictReport {
+    pub time: Duration,
+    pub kind: VerdictKind,
+    pub seq: Option<u64>, // Sequence number of the event, counting accepted events from 1, None for timed verdicts
+    pub source: Option<String>, // Label of the event's source, if it was given one
+    pub inputs: Vec<(String, Value)>, // Input values the interpreter accepted for the event, by input name
+    pub outputs: Vec<OutputChange>,
+    pub triggers: Vec<TriggerChange>,
+    // Named outputs whose value differs from their previous value as (name, previous, current), Value::None for an
+    // output's first value. Filled for verdicts received by the process_* methods and tick, empty for other reports.
+    pub changed: Vec<(String, Value, Value)>,
+}
+
+impl VerdictReport {
+    pub fn from_queued(verdict: QueuedVerdict<TotalIncremental, RelativeFloat>, ir: &RtLolaMir) -> Self {
+        let mut outputs = Vec::new();
+        let mut triggers = Vec::new();
+        let inputs = verdict.verdict.inputs.into_iter()
+            .map(|(idx, value)| (ir.inputs[idx].name.clone(), value))
+            .collect();
+
+        for (out_idx, changes) in verdict.verdict.outputs {
+            match &ir.outputs[out_idx].kind {
+                OutputKind::NamedOutput(name) => {
+                    outputs.extend(changes.into_iter().map(|change| OutputChange { name: name.clone(), change }));
+                },
+                OutputKind::Trigger(index) => {
+                    triggers.extend(changes.into_iter().map(|change| TriggerChange { index: *index, change }));
+                },
+            }
+        }
+
+        Self {
+            time: verdict.ts,
+            kind: verdict.kind,
+            seq: None,
+            source: None,
+            inputs,
+            outputs,
+            triggers,
+            changed: Vec::new(),
+        }
+    }
+
+    // Triggers that produced a value in this verdict, i.e. that fired
+    pub fn fired_triggers(&self) -> impl Iterator<Item = (usize, &Value)> {
+        self.triggers.iter().filter_map(|t| match &t.change {
+            Change::Value(_, val) => Some((t.index, val)),
+            _ => None,
+        })
+    }
+}
+
+// Sequence numbers and source labels of submitted events in submission order, until the verdict of the event is consumed
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PendingSources(Arc<Mutex<Pending>>);
+
+type PendingEvent = (u64, Option<String>); // Sequence number and source label
+
+#[derive(Debug, Default)]
+struct Pending {
+    events: VecDeque<PendingEvent>,
+    tick: Option<Duration>, // Time of the last tick while its own verdict may still be queued
+}
+
+impl PendingSources {
+    pub(crate) fn push(&self, seq: u64, source: Option<&str>) {
+        self.0.lock().unwrap().events.push_back((seq, source.map(str::to_string)));
+    }
+
+    // Timed verdicts do not belong to a submitted event
+    pub(crate) fn take(&self, kind: VerdictKind) -> (Option<u64>, Option<String>) {
+        match kind {
+            VerdictKind::Event => match self.0.lock().unwrap().events.pop_front() {
+                Some((seq, source)) => (Some(seq), source),
+                None => (None, None),
+            },
+            VerdictKind::Timed => (None, None),
+        }
+    }
+
+    // Expect the own verdict of a tick's event at `time`, see RtlolaMonitor::tick
+    pub(crate) fn push_tick(&self, time: Duration) {
+        self.0.lock().unwrap().tick = Some(time);
+    }
+
+    // Whether a verdict is the own verdict of the last tick's event, which is dropped instead of reported.
+    // That event has no input values, so it only has a verdict if a stream is evaluated on every event. The first
+    // verdict after the tick's timed verdicts settles the tick either way.
+    pub(crate) fn is_tick_verdict(&self, verdict: &QueuedVerdict<TotalIncremental, RelativeFloat>) -> bool {
+        let mut pending = self.0.lock().unwrap();
+        let Some(tick) = pending.tick else {
+            return false;
+        };
+        if matches!(verdict.kind, VerdictKind::Timed) && verdict.ts < tick {
+            return false;
+        }
+        pending.tick = None;
+        matches!(verdict.kind, VerdictKind::Event) && verdict.ts == tick && verdict.verdict.inputs.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{ball_event, ball_monitor, BALL_INPUTS};
+
+    #[test]
+    fn report_inputs_are_named_by_the_spec() {
+        let mut monitor = ball_monitor();
+        let event = ball_event(1.5, -2.0, 20.0);
+        let report = monitor.process_event(event.clone(), Some(Duration::from_secs(1))).unwrap();
+        let expected = BALL_INPUTS.into_iter().map(String::from).zip(event).collect::<Vec<_>>();
+        assert_eq!(report.inputs, expected);
+    }
+}
@@ -0,0 +1,89 @@
+use std::time::Duration;
+use rtlola_interpreter::Value;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use crate::error::RtlolaError;
+use crate::rtlola_monitor::{event_time, RtlolaMonitor};
+use crate::verdict::VerdictReport;
+
+// Whether a trace satisfies a specification, returned by verify_trace
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct TraceVerdict {
+    pub passed: bool, // No trigger fired
+    pub violations: Vec<TraceViolation>, // Every trigger that fired, in verdict order
+    pub events: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct TraceViolation {
+    pub trigger: usize, // Trigger index
+    pub message: String,
+    pub time: Duration,
+}
+
+// Monitor a complete trace of (inputs, time in seconds) events with a fresh offline monitor for the spec text
+// and report whether any trigger fired. Fails with the error of the first event that cannot be processed,
+// RtlolaError::InvalidTime for a negative, NaN or infinite time.
+pub fn verify_trace(spec: &str, input_names: &[&str], events: Vec<(Vec<Value>, f64)>) -> Result<TraceVerdict, RtlolaError> {
+    let mut monitor = RtlolaMonitor::builder("", input_names)
+        .spec_text(spec)
+        .build()?;
+    monitor.start()?;
+
+    let mut verdict = TraceVerdict { events: events.len() as u64, ..TraceVerdict::default() };
+    for (inputs, time) in events {
+        let report = monitor.process_event(inputs, Some(event_time(time)?))?;
+        verdict.record(&report);
+    }
+    // Timed verdicts of the last events that no process_event call took
+    for report in monitor.queued_reports() {
+        verdict.record(&report);
+    }
+    verdict.passed = verdict.violations.is_empty();
+    Ok(verdict)
+}
+
+impl TraceVerdict {
+    fn record(&mut self, report: &VerdictReport) {
+        self.violations.extend(report.fired_triggers().map(|(trigger, message)| TraceViolation {
+            trigger,
+            message: message.to_string(),
+            time: report.time,
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rtlola_monitor::make_float;
+
+    const SPEC: &str = "input speed: Float64\ntrigger speed > 100.0 \"too fast\"";
+
+    fn trace(speeds: &[(f64, f64)]) -> Vec<(Vec<Value>, f64)> {
+        speeds.iter().map(|&(speed, time)| (vec![make_float(speed).unwrap()], time)).collect()
+    }
+
+    #[test]
+    fn passing_trace() {
+        let verdict = verify_trace(SPEC, &["speed"], trace(&[(50.0, 1.0), (90.0, 2.0)])).unwrap();
+        assert!(verdict.passed);
+        assert!(verdict.violations.is_empty());
+        assert_eq!(verdict.events, 2);
+    }
+
+    #[test]
+    fn failing_trace() {
+        let verdict = verify_trace(SPEC, &["speed"], trace(&[(50.0, 1.0), (120.0, 2.0), (80.0, 3.0)])).unwrap();
+        assert!(!verdict.passed);
+        assert_eq!(verdict.violations, [TraceViolation { trigger: 0, message: "too fast".to_string(), time: Duration::from_secs(2) }]);
+    }
+
+    #[test]
+    fn invalid_time() {
+        let result = verify_trace(SPEC, &["speed"], trace(&[(50.0, 1.0), (60.0, f64::NAN)]));
+        assert!(matches!(result, Err(RtlolaError::InvalidTime(_))));
+    }
+}
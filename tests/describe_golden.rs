@@ -0,0 +1,11 @@
+// describe_json of the ball spec against the checked-in document in tests/golden
+#![cfg(feature = "serde")]
+use rtlola_integration::RtlolaMonitor;
+
+#[test]
+fn ball_spec_description_matches_golden_file() {
+    let monitor = RtlolaMonitor::new("src/ball_spec.lola", 500, &["height", "velocity", "temperature"]).unwrap();
+    let actual: serde_json::Value = serde_json::from_str(&monitor.describe_json()).unwrap();
+    let golden: serde_json::Value = serde_json::from_str(include_str!("golden/ball_describe.json")).unwrap();
+    assert_eq!(actual, golden, "describe_json changed:\n{}", serde_json::to_string_pretty(&actual).unwrap());
+}
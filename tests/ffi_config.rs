@@ -0,0 +1,70 @@
+// Creating monitors through the C API from a single RTLolaConfig with the spec given inline
+use std::ffi::{c_char, CStr, CString};
+use rtlola_integration::{
+    rtlola_free_string, rtlola_get_trigger_count, rtlola_last_error_code, rtlola_monitor_free, rtlola_monitor_new_from_config,
+    rtlola_monitor_start, rtlola_process_inputs, rtlola_spec_source, RTLolaConfig, RTLolaInput, RTLolaMonitorHandle,
+    RTLolaValueData, RTLolaValueType,
+};
+
+const SPEC: &str = "input speed: Float64\ntrigger speed > 10.0 \"Too fast\"\n";
+
+fn config(spec_ptr: *const u8, spec_len: usize, input_names: &[*const c_char]) -> RTLolaConfig {
+    RTLolaConfig {
+        spec_path: std::ptr::null(),
+        spec_ptr,
+        spec_len,
+        timeout_ms: 500,
+        input_names: input_names.as_ptr(),
+        num_inputs: input_names.len() as u64,
+        online: false,
+        nanos_time: false,
+    }
+}
+
+fn speed(x: f64) -> RTLolaInput {
+    RTLolaInput { name: std::ptr::null(), type_: RTLolaValueType::Float64 as u32, value: RTLolaValueData { float64_val: x } }
+}
+
+fn process(handle: *mut RTLolaMonitorHandle, x: f64, time: f64) -> String {
+    let verdict = rtlola_process_inputs(handle, &speed(x), 1, time);
+    let text = unsafe { CStr::from_ptr(verdict) }.to_str().unwrap().to_string();
+    rtlola_free_string(verdict);
+    text
+}
+
+#[test]
+fn monitor_from_inline_spec() {
+    // Only the first spec_len bytes are the spec, the buffer is not null-terminated
+    let mut buffer = SPEC.as_bytes().to_vec();
+    buffer.extend_from_slice(b"garbage");
+    let name = CString::new("speed").unwrap();
+    let handle = rtlola_monitor_new_from_config(&config(buffer.as_ptr(), SPEC.len(), &[name.as_ptr()]));
+    assert!(!handle.is_null());
+    assert!(rtlola_monitor_start(handle));
+
+    assert_eq!(unsafe { CStr::from_ptr(rtlola_spec_source(handle)) }.to_str().unwrap(), SPEC);
+    assert_eq!(rtlola_get_trigger_count(handle), 1);
+    assert!(!process(handle, 5.0, 1.0).contains("Too fast"));
+    assert!(process(handle, 12.0, 2.0).contains("Too fast"));
+    rtlola_monitor_free(handle);
+}
+
+#[test]
+fn invalid_inline_specs_are_rejected() {
+    let name = CString::new("speed").unwrap();
+    let names = [name.as_ptr()];
+
+    let invalid_utf8 = [0xff, 0xfe];
+    assert!(rtlola_monitor_new_from_config(&config(invalid_utf8.as_ptr(), invalid_utf8.len(), &names)).is_null());
+    assert_eq!(rtlola_last_error_code(), -101);
+
+    assert!(rtlola_monitor_new_from_config(&config(std::ptr::null(), SPEC.len(), &names)).is_null());
+    assert_eq!(rtlola_last_error_code(), -101);
+
+    assert!(rtlola_monitor_new_from_config(&config(std::ptr::null(), 0, &names)).is_null());
+    assert_eq!(rtlola_last_error_code(), -100);
+
+    let unparsable = "input speed: Float64\ntrigger speed >";
+    assert!(rtlola_monitor_new_from_config(&config(unparsable.as_ptr(), unparsable.len(), &names)).is_null());
+    assert_eq!(rtlola_last_error_code(), -2);
+}
@@ -0,0 +1,73 @@
+// Freeing handles through the C API, in its own test binary so the allocation counter only sees this test
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::ffi::{c_char, CString};
+use std::sync::atomic::{AtomicIsize, Ordering};
+use rtlola_integration::{rtlola_monitor_free, rtlola_monitor_new, RTLolaMonitorHandle};
+
+struct Counting;
+
+static LIVE: AtomicIsize = AtomicIsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            LIVE.fetch_add(layout.size() as isize, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        LIVE.fetch_sub(layout.size() as isize, Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+fn new_monitor() -> *mut RTLolaMonitorHandle {
+    let spec = CString::new("src/ball_spec.lola").unwrap();
+    let names = ["height", "velocity", "temperature"].map(|name| CString::new(name).unwrap());
+    let name_ptrs = names.iter().map(|name| name.as_ptr()).collect::<Vec<*const c_char>>();
+    let handle = rtlola_monitor_new(spec.as_ptr(), 500, name_ptrs.as_ptr(), 3);
+    assert!(!handle.is_null());
+    handle
+}
+
+#[test]
+fn free_releases_the_monitor_and_ignores_double_frees() {
+    // Warm up lazily allocated statics, e.g. the registry of live handles
+    rtlola_monitor_free(new_monitor());
+
+    let before = LIVE.load(Ordering::Relaxed);
+    let monitor_size = {
+        let handle = new_monitor();
+        let size = LIVE.load(Ordering::Relaxed) - before;
+        rtlola_monitor_free(handle);
+        rtlola_monitor_free(handle);
+        size
+    };
+    for _ in 0..20 {
+        let handle = new_monitor();
+        rtlola_monitor_free(handle);
+        rtlola_monitor_free(handle);
+    }
+    let leaked = LIVE.load(Ordering::Relaxed) - before;
+    // A leaked monitor would leave at least its own allocations behind on every iteration
+    assert!(leaked < monitor_size, "{} bytes still allocated, a monitor takes {}", leaked, monitor_size);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn finalize_json_frees_the_handle_once() {
+    use rtlola_integration::{rtlola_free_string, rtlola_monitor_finalize_json};
+
+    let handle = new_monitor();
+    let json = rtlola_monitor_finalize_json(handle);
+    assert!(!json.is_null());
+    rtlola_free_string(json);
+    // The handle is no longer live, so neither call may touch it again
+    assert!(rtlola_monitor_finalize_json(handle).is_null());
+    rtlola_monitor_free(handle);
+}
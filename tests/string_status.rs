@@ -0,0 +1,38 @@
+// String inputs and outputs end to end through src/status_spec.lola
+use std::time::Duration;
+use rtlola_integration::{ColorChoice, RtlolaMonitor, Value};
+
+fn status_monitor() -> RtlolaMonitor {
+    let mut monitor = RtlolaMonitor::builder("src/status_spec.lola", &["status", "code"])
+        .timeout_ms(500)
+        .color(ColorChoice::Never)
+        .build()
+        .unwrap();
+    monitor.start().unwrap();
+    monitor
+}
+
+fn status_event(status: &str, code: u64) -> Vec<Value> {
+    vec![Value::Str(status.into()), Value::Unsigned(code)]
+}
+
+#[test]
+fn triggers_react_to_string_inputs() {
+    let mut monitor = status_monitor();
+    let events = [("ok", 200, vec![]), ("error", 200, vec![0]), ("späte Antwort", 503, vec![1])];
+    for (i, (status, code, expected)) in events.into_iter().enumerate() {
+        let report = monitor.process_event(status_event(status, code), Some(Duration::from_secs(i as u64 + 1))).unwrap();
+        let fired = report.fired_triggers().map(|(idx, _)| idx).collect::<Vec<_>>();
+        assert_eq!(fired, expected, "status {:?}", status);
+    }
+    assert_eq!(monitor.current_output("last_status"), Some(Value::Str("späte Antwort".into())));
+}
+
+#[test]
+fn verdict_strings_show_string_values_as_they_are() {
+    let mut monitor = status_monitor();
+    let verdict = monitor.process_event_verdict(status_event("error", 0), Some(Duration::from_secs(1))).unwrap();
+    assert!(verdict.contains("[Input] [status] = error\n"), "{}", verdict);
+    assert!(verdict.contains("[Output][last_status] [Value] =  error\n"), "{}", verdict);
+    assert!(verdict.contains("[Trigger] [#0] Device reported an error\n"), "{}", verdict);
+}